@@ -0,0 +1,289 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    symbols,
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType},
+    Frame,
+};
+
+use crate::core::Metric;
+
+/// The same metrics over a previous window, drawn dimmed behind the current ones.
+pub struct Overlay<'a> {
+    pub label: &'a str,
+    pub metrics: &'a [Metric],
+}
+
+// favor width, count of 6 charts = 2x3, count of 5 = 2x2 + 1, count of 4 = 2x2, count of 3 = 1x3, count of 2 = 1x2, count of 1 = 1x1,
+// beyond 6 charts add rows of 2
+pub fn render_charts(frame: &mut Frame, area: Rect, data: &[Metric], overlay: Option<&Overlay>) {
+    let (x, y) = match data.len() {
+        0 | 1 => (1, 1),
+        2 => (1, 2),
+        3 => (1, 3),
+        4 => (2, 2),
+        5 => (2, 2),
+        n => (2, n.div_ceil(2)),
+    };
+
+    let vertical_chart_chunks = Layout::default()
+        .constraints(
+            (0..x)
+                .map(|_| Constraint::Percentage(100 / x as u16))
+                .collect::<Vec<_>>(),
+        )
+        .direction(ratatui::layout::Direction::Horizontal)
+        .split(area);
+
+    let horizontal_chart_chunks: Vec<_> = vertical_chart_chunks
+        .iter()
+        .map(|chunk| {
+            Layout::default()
+                .constraints(
+                    (0..y)
+                        .map(|_| Constraint::Percentage(100 / y as u16))
+                        .collect::<Vec<_>>(),
+                )
+                .direction(ratatui::layout::Direction::Vertical)
+                .split(*chunk)
+        })
+        .collect();
+
+    data.iter()
+        .take(x * y)
+        .enumerate()
+        .for_each(|(index, data)| {
+            let chunk = horizontal_chart_chunks[index / y][index % y];
+            let previous = overlay.and_then(|overlay| {
+                overlay
+                    .metrics
+                    .iter()
+                    .find(|m| m.name == data.name)
+                    .map(|metric| (overlay.label, metric))
+            });
+            render_chart(frame, chunk, data, previous, &[]);
+        });
+}
+
+/// A horizontal line drawn across a chart, such as a concurrency limit.
+pub struct ReferenceLine {
+    pub name: String,
+    pub value: f64,
+    pub color: Color,
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len().max(1) as f64
+}
+
+// the change of the average value against the baseline, as a percentage
+fn delta(current: &Metric, baseline: &Metric) -> Option<f64> {
+    let previous = mean(&baseline.values);
+    if previous == 0.0 {
+        return None;
+    }
+    Some((mean(&current.values) - previous) / previous * 100.0)
+}
+
+pub fn render_chart(
+    frame: &mut Frame,
+    area: Rect,
+    data: &Metric,
+    baseline: Option<(&str, &Metric)>,
+    references: &[ReferenceLine],
+) {
+    let dataset: Vec<_> = data
+        .timestamps
+        .iter()
+        .zip(data.values.iter())
+        .map(|(timestamp, value)| (*timestamp as f64, *value))
+        .collect();
+
+    let (min_x, max_x) = dataset
+        .iter()
+        .fold((f64::MAX, f64::MIN), |(min, max), (x, _)| {
+            (min.min(*x), max.max(*x))
+        });
+
+    let (min_y, max_y) = dataset
+        .iter()
+        .fold((f64::MAX, f64::MIN), |(min, max), (_, y)| {
+            (min.min(*y), max.max(*y))
+        });
+
+    let baseline_data: Vec<(f64, f64)> = baseline
+        .map(|(_, metric)| {
+            metric
+                .timestamps
+                .iter()
+                .zip(metric.values.iter())
+                .map(|(timestamp, value)| (*timestamp as f64, *value))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // make sure the reference lines and baseline are within the bounds of the chart
+    let max_y = references
+        .iter()
+        .map(|reference| reference.value)
+        .chain(baseline_data.iter().map(|(_, y)| *y))
+        .fold(max_y, f64::max);
+
+    let reference_data: Vec<Vec<(f64, f64)>> = references
+        .iter()
+        .map(|reference| vec![(min_x, reference.value), (max_x, reference.value)])
+        .collect();
+
+    let line_style = match data.name.as_str() {
+        "errors" | "error_rate" | "throttle_rate" => Style::default().red(),
+        _ => Style::default().green(),
+    };
+
+    let mut datasets = vec![];
+
+    // drawn first so the current line stays on top
+    if let Some((label, _)) = baseline {
+        datasets.push(
+            Dataset::default()
+                .name(label.to_string())
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::DarkGray))
+                .data(&baseline_data),
+        );
+    }
+
+    datasets.push(
+        Dataset::default()
+            .name(data.name.clone())
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(line_style)
+            .data(&dataset),
+    );
+
+    datasets.extend(
+        references
+            .iter()
+            .zip(reference_data.iter())
+            .map(|(reference, data)| {
+                Dataset::default()
+                    .name(format!("{} ({})", reference.name, reference.value))
+                    .marker(symbols::Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(Style::default().fg(reference.color))
+                    .data(data)
+            }),
+    );
+
+    // Create the X axis and define its properties
+    let x_axis = Axis::default()
+        .title("Time".white())
+        .style(Style::default().gray())
+        .bounds([min_x, max_x])
+        .labels(vec!["A".into(), "B".into(), "C".into()]);
+
+    // Create the Y axis and define its properties
+    let y_axis = Axis::default()
+        .title("Count".white())
+        .style(Style::default().gray())
+        .bounds([0.0, max_y])
+        .labels(vec![
+            min_y.to_string().into(),
+            (min_y + (max_y - min_y) / 2.0).to_string().into(),
+            max_y.to_string().into(),
+        ]);
+
+    let title = match baseline {
+        Some((label, metric)) => match delta(data, metric) {
+            Some(delta) => format!("{} ({:+.1}% vs {})", data.name, delta, label),
+            None => format!("{} (n/a vs {})", data.name, label),
+        },
+        None => data.name.to_string(),
+    };
+
+    // Create the chart and link all the parts together
+    let chart = Chart::new(datasets)
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .x_axis(x_axis)
+        .y_axis(y_axis);
+
+    frame.render_widget(chart, area);
+}
+
+/// One of several metrics drawn on the same chart.
+pub struct Series<'a> {
+    pub name: String,
+    pub metric: &'a Metric,
+    pub color: Color,
+}
+
+/// Draw several series on one chart, the y axis reaches at least `max_y` so separate charts
+/// can share a scale.
+pub fn render_series_chart(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    series: &[Series],
+    max_y: f64,
+) {
+    let data: Vec<Vec<(f64, f64)>> = series
+        .iter()
+        .map(|s| {
+            s.metric
+                .timestamps
+                .iter()
+                .zip(s.metric.values.iter())
+                .map(|(timestamp, value)| (*timestamp as f64, *value))
+                .collect()
+        })
+        .collect();
+
+    let (min_x, max_x) = data
+        .iter()
+        .flatten()
+        .fold((f64::MAX, f64::MIN), |(min, max), (x, _)| {
+            (min.min(*x), max.max(*x))
+        });
+
+    let max_y = data.iter().flatten().fold(max_y, |max, (_, y)| max.max(*y));
+
+    let datasets = series
+        .iter()
+        .zip(data.iter())
+        .map(|(s, data)| {
+            Dataset::default()
+                .name(s.name.clone())
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(s.color))
+                .data(data)
+        })
+        .collect::<Vec<_>>();
+
+    let x_axis = Axis::default()
+        .title("Time".white())
+        .style(Style::default().gray())
+        .bounds([min_x, max_x]);
+
+    let y_axis = Axis::default()
+        .title("Count".white())
+        .style(Style::default().gray())
+        .bounds([0.0, max_y])
+        .labels(vec![
+            "0".into(),
+            (max_y / 2.0).to_string().into(),
+            max_y.to_string().into(),
+        ]);
+
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(title.to_string())
+                .borders(Borders::ALL),
+        )
+        .x_axis(x_axis)
+        .y_axis(y_axis);
+
+    frame.render_widget(chart, area);
+}
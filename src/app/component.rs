@@ -1,9 +1,11 @@
 use crate::core::{Action, State};
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
 use ratatui::{
+    layout::{Constraint, Direction, Layout},
     prelude::{Backend, Rect},
     style::{Color, Style, Stylize},
-    widgets::{Block, Borders, Paragraph},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
 
@@ -18,6 +20,10 @@ pub trait Component {
     fn name(&self) -> &str;
 
     fn handle_key_event(&mut self, key: KeyEvent);
+
+    /// Called on every rendering tick with the area the page is drawn in, for work that waits
+    /// on time passing or on how much fits on screen, rendering itself changes nothing.
+    fn handle_tick(&mut self, _area: Rect) {}
 }
 
 pub trait ComponentRender<Props> {
@@ -166,3 +172,50 @@ impl ComponentRender<RenderProps> for InputBox {
         }
     }
 }
+
+/// A popup asking the user to confirm an action before it is performed, the owning page
+/// decides what happens on [y] and [n].
+pub struct ConfirmDialog {
+    pub title: String,
+    pub lines: Vec<String>,
+}
+
+pub(crate) fn centered_area(r: Rect, percent_x: u16, height: u16) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(height.min(r.height)),
+            Constraint::Min(0),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+impl ComponentRender<()> for ConfirmDialog {
+    fn render(&self, frame: &mut Frame, _: ()) {
+        let area = centered_area(frame.size(), 60, self.lines.len() as u16 + 4);
+
+        let mut lines: Vec<Line> = self.lines.iter().map(|l| Line::from(l.as_str())).collect();
+        lines.push(Line::from(""));
+        lines.push(Line::from("[y] confirm, [n] cancel".yellow()));
+
+        let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .title(self.title.as_str())
+                .borders(Borders::ALL)
+                .fg(Color::Yellow),
+        );
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(paragraph, area);
+    }
+}
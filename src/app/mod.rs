@@ -1,3 +1,4 @@
+mod chart;
 mod component;
 mod pages;
 use std::{
@@ -81,7 +82,11 @@ impl App {
 
         loop {
             tokio::select! {
-                _ = ticker.tick() => (),
+                _ = ticker.tick() => {
+                    if let Ok(area) = terminal.size() {
+                        self.router.handle_tick(area);
+                    }
+                }
                 _ = self.terminator_rx.recv() => {
                     self.action_tx.send(Action::Quit).unwrap();
                     break;
@@ -0,0 +1,248 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Text,
+    Frame,
+};
+
+use crate::{
+    app::{
+        chart::{render_series_chart, Series},
+        component::{Component, ComponentRender},
+    },
+    core::{Action, Lambda, Metric, State},
+};
+
+pub struct CompareProps {
+    left: Option<Lambda>,
+    right: Option<Lambda>,
+    metrics: Vec<(Metric, Metric)>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CompareLayout {
+    /// both functions on shared charts
+    Overlay,
+    /// a column of charts per function, on the same scale
+    Mirrored,
+}
+
+const LEFT_COLOR: Color = Color::Green;
+const RIGHT_COLOR: Color = Color::Cyan;
+
+pub struct ComparePage {
+    action_tx: tokio::sync::mpsc::UnboundedSender<Action>,
+    props: CompareProps,
+    layout: CompareLayout,
+}
+
+fn props_from_state(state: &State) -> CompareProps {
+    if let State::Compare(compare) = state {
+        CompareProps {
+            left: Some(compare.left.clone()),
+            right: Some(compare.right.clone()),
+            metrics: compare.metrics.clone(),
+        }
+    } else {
+        CompareProps {
+            left: None,
+            right: None,
+            metrics: vec![],
+        }
+    }
+}
+
+fn split(area: Rect, direction: Direction, count: usize) -> std::rc::Rc<[Rect]> {
+    Layout::default()
+        .constraints(
+            (0..count)
+                .map(|_| Constraint::Ratio(1, count.max(1) as u32))
+                .collect::<Vec<_>>(),
+        )
+        .direction(direction)
+        .split(area)
+}
+
+fn name(lambda: &Option<Lambda>) -> String {
+    lambda.as_ref().map(|l| l.name.clone()).unwrap_or_default()
+}
+
+impl ComparePage {
+    fn render_overlay(&self, frame: &mut Frame, area: Rect) {
+        let rows = split(
+            area,
+            Direction::Vertical,
+            self.props.metrics.len().div_ceil(2),
+        );
+
+        self.props
+            .metrics
+            .iter()
+            .enumerate()
+            .for_each(|(index, (left, right))| {
+                let columns = split(rows[index / 2], Direction::Horizontal, 2);
+                render_series_chart(
+                    frame,
+                    columns[index % 2],
+                    &left.name,
+                    &[
+                        Series {
+                            name: name(&self.props.left),
+                            metric: left,
+                            color: LEFT_COLOR,
+                        },
+                        Series {
+                            name: name(&self.props.right),
+                            metric: right,
+                            color: RIGHT_COLOR,
+                        },
+                    ],
+                    0.0,
+                );
+            });
+    }
+
+    fn render_mirrored(&self, frame: &mut Frame, area: Rect) {
+        let rows = split(area, Direction::Vertical, self.props.metrics.len());
+
+        self.props
+            .metrics
+            .iter()
+            .zip(rows.iter())
+            .for_each(|((left, right), row)| {
+                let columns = split(*row, Direction::Horizontal, 2);
+                // the larger of the two sets the scale of both panes
+                let max_y = left
+                    .values
+                    .iter()
+                    .chain(right.values.iter())
+                    .fold(0.0, |max: f64, value| max.max(*value));
+
+                for (metric, lambda, color, column) in [
+                    (left, &self.props.left, LEFT_COLOR, columns[0]),
+                    (right, &self.props.right, RIGHT_COLOR, columns[1]),
+                ] {
+                    render_series_chart(
+                        frame,
+                        column,
+                        &format!("{} {}", name(lambda), metric.name),
+                        &[Series {
+                            name: metric.name.clone(),
+                            metric,
+                            color,
+                        }],
+                        max_y,
+                    );
+                }
+            });
+    }
+}
+
+impl Component for ComparePage {
+    fn new(state: &State, action_tx: &tokio::sync::mpsc::UnboundedSender<Action>) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            action_tx: action_tx.clone(),
+            props: props_from_state(state),
+            layout: CompareLayout::Overlay,
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Compare"
+    }
+
+    fn move_with_state(self, state: &State) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            action_tx: self.action_tx,
+            props: props_from_state(state),
+            layout: self.layout,
+        }
+    }
+
+    fn handle_key_event(&mut self, key: crossterm::event::KeyEvent) {
+        match key.code {
+            crossterm::event::KeyCode::Char('q') => {
+                self.action_tx.send(Action::Quit).unwrap();
+            }
+            crossterm::event::KeyCode::Esc => {
+                self.action_tx.send(Action::Search).unwrap();
+            }
+            crossterm::event::KeyCode::Char('o') => {
+                self.layout = match self.layout {
+                    CompareLayout::Overlay => CompareLayout::Mirrored,
+                    CompareLayout::Mirrored => CompareLayout::Overlay,
+                };
+            }
+            crossterm::event::KeyCode::Char('r') => {
+                if let (Some(left), Some(right)) = (&self.props.left, &self.props.right) {
+                    self.action_tx
+                        .send(Action::CompareMetrics {
+                            left: left.clone(),
+                            right: right.clone(),
+                        })
+                        .unwrap();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl ComponentRender<()> for ComparePage {
+    fn render(&self, frame: &mut Frame, _: ()) {
+        let chunks = Layout::default()
+            .constraints(
+                [
+                    Constraint::Length(1),
+                    Constraint::Min(1),
+                    Constraint::Length(1),
+                ]
+                .as_ref(),
+            )
+            .direction(Direction::Vertical)
+            .split(frame.size());
+
+        let title_chunks = split(chunks[0], Direction::Horizontal, 2);
+
+        frame.render_widget(
+            Text::styled(
+                name(&self.props.left),
+                Style::default().fg(LEFT_COLOR).bold().bg(Color::DarkGray),
+            ),
+            title_chunks[0],
+        );
+
+        frame.render_widget(
+            Text::styled(
+                name(&self.props.right),
+                Style::default().fg(RIGHT_COLOR).bold().bg(Color::DarkGray),
+            ),
+            title_chunks[1],
+        );
+
+        match self.layout {
+            CompareLayout::Overlay => self.render_overlay(frame, chunks[1]),
+            CompareLayout::Mirrored => self.render_mirrored(frame, chunks[1]),
+        }
+
+        let help_text = Text::styled(
+            match self.layout {
+                CompareLayout::Overlay => {
+                    "help: [q] quit, [esc] search, [o] mirrored panes, [r] refresh"
+                }
+                CompareLayout::Mirrored => {
+                    "help: [q] quit, [esc] search, [o] overlay charts, [r] refresh"
+                }
+            },
+            Style::default().fg(Color::White).bg(Color::DarkGray),
+        );
+
+        frame.render_widget(help_text, chunks[2]);
+    }
+}
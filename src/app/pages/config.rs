@@ -0,0 +1,578 @@
+use std::collections::HashSet;
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Text,
+    widgets::{Block, Borders, Clear, Row, Table},
+    Frame,
+};
+
+use crate::{
+    app::component::{Component, ComponentRender, ConfirmDialog, InputBox, RenderProps},
+    core::{Action, FunctionConfiguration, Lambda, State},
+};
+
+pub struct ConfigProps {
+    lambda: Option<Lambda>,
+    config: FunctionConfiguration,
+    updating: bool,
+    status: Option<String>,
+}
+
+/// The setting currently being typed into the input box.
+#[derive(Debug, Clone)]
+pub enum ConfigEdit {
+    Memory,
+    Timeout,
+    EphemeralStorage,
+    Variable { key: String },
+    NewVariable,
+}
+
+/// The fixed settings shown above the environment variables.
+const SETTINGS: usize = 3;
+
+const MASK: &str = "********";
+
+pub struct ConfigPage {
+    action_tx: tokio::sync::mpsc::UnboundedSender<Action>,
+    props: ConfigProps,
+    /// the configuration as edited, applied once confirmed
+    draft: FunctionConfiguration,
+    selected: usize,
+    input_box: InputBox,
+    editing: Option<ConfigEdit>,
+    /// environment variables whose values are shown
+    revealed: HashSet<String>,
+    confirming: bool,
+}
+
+fn props_from_state(state: &State) -> ConfigProps {
+    if let State::Config(config) = state {
+        ConfigProps {
+            lambda: Some(config.lambda.clone()),
+            config: config.config.clone(),
+            updating: config.updating,
+            status: config.status.clone(),
+        }
+    } else {
+        ConfigProps {
+            lambda: None,
+            config: FunctionConfiguration::default(),
+            updating: false,
+            status: None,
+        }
+    }
+}
+
+fn variable<'a>(config: &'a FunctionConfiguration, key: &str) -> Option<&'a String> {
+    config
+        .environment
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v)
+}
+
+impl ConfigPage {
+    fn masked(&self, key: &str, value: &str) -> String {
+        if self.revealed.contains(key) {
+            value.to_string()
+        } else {
+            MASK.to_string()
+        }
+    }
+
+    fn row_count(&self) -> usize {
+        SETTINGS + self.draft.environment.len()
+    }
+
+    fn start_edit(&mut self) {
+        let (edit, text) = match self.selected {
+            0 => (ConfigEdit::Memory, self.draft.memory.to_string()),
+            1 => (ConfigEdit::Timeout, self.draft.timeout.to_string()),
+            2 => (
+                ConfigEdit::EphemeralStorage,
+                self.draft.ephemeral_storage.to_string(),
+            ),
+            index => match self.draft.environment.get(index - SETTINGS) {
+                Some((key, value)) => {
+                    // editing a value reveals it, there is no point typing blind
+                    self.revealed.insert(key.clone());
+                    (ConfigEdit::Variable { key: key.clone() }, value.clone())
+                }
+                None => return,
+            },
+        };
+
+        self.input_box.set_text(&text);
+        self.editing = Some(edit);
+    }
+
+    fn submit_edit(&mut self) {
+        let text = self.input_box.text().to_string();
+        let number = text.trim().parse::<i64>().ok();
+
+        let valid = match &self.editing {
+            Some(ConfigEdit::Memory) => match number.filter(|x| (128..=10240).contains(x)) {
+                Some(memory) => {
+                    self.draft.memory = memory;
+                    true
+                }
+                None => false,
+            },
+            Some(ConfigEdit::Timeout) => match number.filter(|x| (1..=900).contains(x)) {
+                Some(timeout) => {
+                    self.draft.timeout = timeout;
+                    true
+                }
+                None => false,
+            },
+            Some(ConfigEdit::EphemeralStorage) => {
+                match number.filter(|x| (512..=10240).contains(x)) {
+                    Some(storage) => {
+                        self.draft.ephemeral_storage = storage;
+                        true
+                    }
+                    None => false,
+                }
+            }
+            Some(ConfigEdit::Variable { key }) => {
+                if let Some((_, value)) = self.draft.environment.iter_mut().find(|(k, _)| k == key)
+                {
+                    *value = text;
+                }
+                true
+            }
+            Some(ConfigEdit::NewVariable) => match text.split_once('=') {
+                Some((key, value)) if !key.trim().is_empty() => {
+                    let key = key.trim().to_string();
+                    self.draft.environment.retain(|(k, _)| *k != key);
+                    self.draft
+                        .environment
+                        .push((key.clone(), value.to_string()));
+                    self.draft.environment.sort_by(|a, b| a.0.cmp(&b.0));
+                    self.selected = SETTINGS
+                        + self
+                            .draft
+                            .environment
+                            .iter()
+                            .position(|(k, _)| *k == key)
+                            .unwrap_or(0);
+                    self.revealed.insert(key);
+                    true
+                }
+                _ => false,
+            },
+            None => false,
+        };
+
+        // keep the input open until a valid value is entered
+        if valid {
+            self.editing = None;
+        }
+    }
+
+    fn remove_variable(&mut self) {
+        if let Some(index) = self.selected.checked_sub(SETTINGS) {
+            if index < self.draft.environment.len() {
+                self.draft.environment.remove(index);
+                self.selected = self.selected.min(self.row_count().saturating_sub(1));
+            }
+        }
+    }
+
+    fn toggle_reveal(&mut self) {
+        if let Some((key, _)) = self
+            .selected
+            .checked_sub(SETTINGS)
+            .and_then(|index| self.draft.environment.get(index))
+        {
+            if !self.revealed.remove(key) {
+                self.revealed.insert(key.clone());
+            }
+        }
+    }
+
+    fn apply(&mut self) {
+        self.confirming = false;
+        if let Some(lambda) = &self.props.lambda {
+            self.action_tx
+                .send(Action::UpdateConfiguration {
+                    lambda: lambda.clone(),
+                    config: self.draft.clone(),
+                })
+                .unwrap();
+        }
+    }
+
+    fn leave(&self) {
+        if let Some(lambda) = &self.props.lambda {
+            self.action_tx
+                .send(Action::PerformSearch {
+                    lambda: lambda.clone(),
+                })
+                .unwrap();
+        }
+    }
+
+    // every difference between the current and edited configuration, values masked unless revealed
+    fn diff(&self) -> Vec<String> {
+        let old = &self.props.config;
+        let new = &self.draft;
+        let mut lines = vec![];
+
+        let settings = [
+            ("Memory", old.memory, new.memory, "MB"),
+            ("Timeout", old.timeout, new.timeout, "s"),
+            (
+                "Ephemeral storage",
+                old.ephemeral_storage,
+                new.ephemeral_storage,
+                "MB",
+            ),
+        ];
+
+        for (name, old, new, unit) in settings {
+            if old != new {
+                lines.push(format!("{}: {} {} -> {} {}", name, old, unit, new, unit));
+            }
+        }
+
+        for (key, value) in &old.environment {
+            match variable(new, key) {
+                None => lines.push(format!("- {}", key)),
+                Some(new_value) if new_value != value => lines.push(format!(
+                    "~ {}: {} -> {}",
+                    key,
+                    self.masked(key, value),
+                    self.masked(key, new_value)
+                )),
+                _ => {}
+            }
+        }
+
+        for (key, value) in &new.environment {
+            if variable(old, key).is_none() {
+                lines.push(format!("+ {}={}", key, self.masked(key, value)));
+            }
+        }
+
+        lines
+    }
+
+    fn confirm_dialog(&self) -> Option<ConfirmDialog> {
+        if !self.confirming {
+            return None;
+        }
+
+        Some(ConfirmDialog {
+            title: "Update Configuration".to_string(),
+            lines: self.diff(),
+        })
+    }
+
+    fn render_input(&self, frame: &mut Frame) {
+        let title = match &self.editing {
+            Some(ConfigEdit::Memory) => "Memory in MB (128 - 10240)".to_string(),
+            Some(ConfigEdit::Timeout) => "Timeout in seconds (1 - 900)".to_string(),
+            Some(ConfigEdit::EphemeralStorage) => {
+                "Ephemeral storage in MB (512 - 10240)".to_string()
+            }
+            Some(ConfigEdit::Variable { key }) => format!("Value of {}", key),
+            Some(ConfigEdit::NewVariable) => "New variable (KEY=value)".to_string(),
+            None => return,
+        };
+
+        let area = frame.size();
+        let width = (area.width / 2).max(title.len() as u16 + 4).min(area.width);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + area.height.saturating_sub(3) / 2,
+            width,
+            3.min(area.height),
+        );
+
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .fg(Color::Yellow);
+        let inner = block.inner(popup);
+
+        frame.render_widget(Clear, popup);
+        frame.render_widget(block, popup);
+
+        self.input_box.render(
+            frame,
+            RenderProps {
+                title: "Configuration".to_string(),
+                area: inner,
+                border_color: Color::Yellow,
+                show_cursor: true,
+            },
+        );
+    }
+
+    fn render_settings(&self, frame: &mut Frame, area: Rect) {
+        let old = &self.props.config;
+        let new = &self.draft;
+
+        let mut rows: Vec<(String, String, String)> = vec![
+            (
+                "Memory (MB)".to_string(),
+                old.memory.to_string(),
+                new.memory.to_string(),
+            ),
+            (
+                "Timeout (s)".to_string(),
+                old.timeout.to_string(),
+                new.timeout.to_string(),
+            ),
+            (
+                "Ephemeral storage (MB)".to_string(),
+                old.ephemeral_storage.to_string(),
+                new.ephemeral_storage.to_string(),
+            ),
+        ];
+
+        rows.extend(new.environment.iter().map(|(key, value)| {
+            (
+                key.clone(),
+                variable(old, key)
+                    .map(|old| self.masked(key, old))
+                    .unwrap_or_default(),
+                self.masked(key, value),
+            )
+        }));
+
+        let rows = rows
+            .into_iter()
+            .enumerate()
+            .map(|(index, (name, current, edited))| {
+                let changed = match index.checked_sub(SETTINGS) {
+                    Some(index) => {
+                        let (key, value) = &new.environment[index];
+                        variable(old, key) != Some(value)
+                    }
+                    None => current != edited,
+                };
+
+                let row = Row::new(vec![name, current, edited]);
+                let row = if changed {
+                    row.style(Style::default().fg(Color::Yellow))
+                } else {
+                    row
+                };
+
+                if index == self.selected {
+                    row.style(Style::default().bg(Color::DarkGray).fg(Color::Yellow))
+                } else {
+                    row
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(30),
+                Constraint::Percentage(35),
+                Constraint::Min(1),
+            ],
+        )
+        .column_spacing(1)
+        .header(
+            Row::new(vec!["Setting", "Current", "New"])
+                .underlined()
+                .bold(),
+        )
+        .block(
+            Block::default()
+                .title("Configuration")
+                .borders(Borders::ALL),
+        );
+
+        frame.render_widget(table, area);
+    }
+}
+
+impl Component for ConfigPage {
+    fn new(state: &State, action_tx: &tokio::sync::mpsc::UnboundedSender<Action>) -> Self
+    where
+        Self: Sized,
+    {
+        let props = props_from_state(state);
+        Self {
+            action_tx: action_tx.clone(),
+            draft: props.config.clone(),
+            props,
+            selected: 0,
+            input_box: InputBox::new(state, action_tx),
+            editing: None,
+            revealed: HashSet::new(),
+            confirming: false,
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Configuration"
+    }
+
+    fn move_with_state(self, state: &State) -> Self
+    where
+        Self: Sized,
+    {
+        // start editing afresh from whatever the function now has
+        if let State::Config(_) = state {
+            let props = props_from_state(state);
+            let opened = self.props.lambda.as_ref().map(|l| &l.name)
+                != props.lambda.as_ref().map(|l| &l.name);
+            Self {
+                action_tx: self.action_tx,
+                draft: props.config.clone(),
+                selected: if opened { 0 } else { self.selected },
+                revealed: if opened {
+                    HashSet::new()
+                } else {
+                    self.revealed
+                },
+                props,
+                input_box: self.input_box,
+                editing: None,
+                confirming: false,
+            }
+        } else {
+            Self {
+                props: props_from_state(state),
+                ..self
+            }
+        }
+    }
+
+    fn handle_key_event(&mut self, key: crossterm::event::KeyEvent) {
+        // the update carries on once the page is left, only editing waits for it
+        if self.props.updating {
+            match key.code {
+                crossterm::event::KeyCode::Char('q') => {
+                    self.action_tx.send(Action::Quit).unwrap();
+                }
+                crossterm::event::KeyCode::Esc => self.leave(),
+                _ => {}
+            }
+            return;
+        }
+
+        if self.confirming {
+            match key.code {
+                crossterm::event::KeyCode::Char('y') => self.apply(),
+                crossterm::event::KeyCode::Char('n') | crossterm::event::KeyCode::Esc => {
+                    self.confirming = false;
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        if self.editing.is_some() {
+            match key.code {
+                crossterm::event::KeyCode::Enter => self.submit_edit(),
+                crossterm::event::KeyCode::Esc => {
+                    self.editing = None;
+                }
+                _ => self.input_box.handle_key_event(key),
+            }
+            return;
+        }
+
+        match key.code {
+            crossterm::event::KeyCode::Char('q') => {
+                self.action_tx.send(Action::Quit).unwrap();
+            }
+            crossterm::event::KeyCode::Esc => self.leave(),
+            crossterm::event::KeyCode::Char('j') | crossterm::event::KeyCode::Down => {
+                self.selected = (self.selected + 1).min(self.row_count().saturating_sub(1));
+            }
+            crossterm::event::KeyCode::Char('k') | crossterm::event::KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            crossterm::event::KeyCode::Char('e') | crossterm::event::KeyCode::Enter => {
+                self.start_edit();
+            }
+            crossterm::event::KeyCode::Char('a') => {
+                self.input_box.reset();
+                self.editing = Some(ConfigEdit::NewVariable);
+            }
+            crossterm::event::KeyCode::Char('x') => self.remove_variable(),
+            crossterm::event::KeyCode::Char('v') => self.toggle_reveal(),
+            crossterm::event::KeyCode::Char('r') => {
+                self.draft = self.props.config.clone();
+                self.selected = self.selected.min(self.row_count().saturating_sub(1));
+            }
+            crossterm::event::KeyCode::Char('w') => {
+                self.confirming = self.draft != self.props.config;
+            }
+            _ => {}
+        }
+    }
+}
+
+impl ComponentRender<()> for ConfigPage {
+    fn render(&self, frame: &mut Frame, _: ()) {
+        let chunks = Layout::default()
+            .constraints(
+                [
+                    Constraint::Length(1),
+                    Constraint::Min(1),
+                    Constraint::Length(if self.props.status.is_some() { 1 } else { 0 }),
+                    Constraint::Length(1),
+                ]
+                .as_ref(),
+            )
+            .direction(Direction::Vertical)
+            .split(frame.size());
+
+        let title = self
+            .props
+            .lambda
+            .as_ref()
+            .map(|lambda| lambda.name.clone())
+            .unwrap_or_default();
+
+        frame.render_widget(
+            Text::styled(
+                title,
+                Style::default().fg(Color::White).bold().bg(Color::DarkGray),
+            ),
+            chunks[0],
+        );
+
+        self.render_settings(frame, chunks[1]);
+
+        if let Some(status) = &self.props.status {
+            frame.render_widget(
+                Text::styled(status.as_str(), Style::default().yellow()),
+                chunks[2],
+            );
+        }
+
+        let help_text = if self.props.updating {
+            Text::styled(
+                "help: [q] quit, [esc] dashboard, the update carries on",
+                Style::default().fg(Color::White).bg(Color::DarkGray),
+            )
+        } else {
+            Text::styled(
+                "help: [q] quit, [esc] dashboard, [j/k] select, [e] edit, [a] add variable, [x] remove variable, [v] reveal value, [r] reset, [w] review and apply",
+                Style::default().fg(Color::White).bg(Color::DarkGray),
+            )
+        };
+
+        frame.render_widget(help_text, chunks[3]);
+
+        self.render_input(frame);
+
+        if let Some(dialog) = self.confirm_dialog() {
+            dialog.render(frame, ());
+        }
+    }
+}
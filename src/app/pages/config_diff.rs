@@ -0,0 +1,357 @@
+use std::collections::{BTreeSet, HashSet};
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style, Stylize},
+    text::Text,
+    widgets::{Block, Borders, Row, Table},
+    Frame,
+};
+
+use crate::{
+    app::component::{Component, ComponentRender},
+    core::{Action, FunctionSnapshot, State},
+    report::layer_name,
+};
+
+pub struct ConfigDiffProps {
+    left: Option<FunctionSnapshot>,
+    right: Option<FunctionSnapshot>,
+}
+
+/// A line of the diff, environment variables carry their key so their values can be revealed.
+struct DiffRow {
+    name: String,
+    left: String,
+    right: String,
+    /// compared on the actual values, which may be masked
+    differs: bool,
+    variable: Option<String>,
+}
+
+impl DiffRow {
+    fn new(name: &str, left: String, right: String) -> Self {
+        Self {
+            name: name.to_string(),
+            differs: left != right,
+            left,
+            right,
+            variable: None,
+        }
+    }
+}
+
+const MASK: &str = "********";
+
+pub struct ConfigDiffPage {
+    action_tx: tokio::sync::mpsc::UnboundedSender<Action>,
+    props: ConfigDiffProps,
+    selected: usize,
+    only_differences: bool,
+    /// environment variables whose values are shown
+    revealed: HashSet<String>,
+}
+
+fn props_from_state(state: &State) -> ConfigDiffProps {
+    if let State::ConfigDiff(diff) = state {
+        ConfigDiffProps {
+            left: Some(diff.left.clone()),
+            right: Some(diff.right.clone()),
+        }
+    } else {
+        ConfigDiffProps {
+            left: None,
+            right: None,
+        }
+    }
+}
+
+fn format_concurrency(concurrency: Option<i64>) -> String {
+    concurrency
+        .map(|c| c.to_string())
+        .unwrap_or("none".to_string())
+}
+
+fn triggers(snapshot: &FunctionSnapshot) -> BTreeSet<String> {
+    snapshot
+        .event_source_mappings
+        .iter()
+        .map(|m| format!("{} {}", m.type_name(), m.name()))
+        .collect()
+}
+
+impl ConfigDiffPage {
+    fn rows(&self) -> Vec<DiffRow> {
+        let (left, right) = match (&self.props.left, &self.props.right) {
+            (Some(left), Some(right)) => (left, right),
+            _ => return vec![],
+        };
+        let (l, r) = (&left.config, &right.config);
+
+        let mut rows = vec![
+            DiffRow::new("Runtime", l.runtime.clone(), r.runtime.clone()),
+            DiffRow::new("Memory (MB)", l.memory.to_string(), r.memory.to_string()),
+            DiffRow::new("Timeout (s)", l.timeout.to_string(), r.timeout.to_string()),
+            DiffRow::new(
+                "Ephemeral storage (MB)",
+                l.ephemeral_storage.to_string(),
+                r.ephemeral_storage.to_string(),
+            ),
+            DiffRow::new("Role", l.role.clone(), r.role.clone()),
+            DiffRow::new(
+                "Reserved concurrency",
+                format_concurrency(left.reserved_concurrency),
+                format_concurrency(right.reserved_concurrency),
+            ),
+            DiffRow::new(
+                "Provisioned concurrency",
+                format_concurrency(left.provisioned_concurrency),
+                format_concurrency(right.provisioned_concurrency),
+            ),
+            DiffRow::new("Subnets", l.subnet_ids.join(", "), r.subnet_ids.join(", ")),
+            DiffRow::new(
+                "Security groups",
+                l.security_group_ids.join(", "),
+                r.security_group_ids.join(", "),
+            ),
+        ];
+
+        // list and set-like settings get a row per entry, so the odd one out stands out
+        let layers = |config: &crate::core::FunctionConfiguration| {
+            config
+                .layers
+                .iter()
+                .map(|arn| layer_name(arn))
+                .collect::<BTreeSet<_>>()
+        };
+        let (left_layers, right_layers) = (layers(l), layers(r));
+        for layer in left_layers.union(&right_layers) {
+            let present = |layers: &BTreeSet<String>| {
+                if layers.contains(layer) {
+                    layer.clone()
+                } else {
+                    "".to_string()
+                }
+            };
+            rows.push(DiffRow::new(
+                "Layer",
+                present(&left_layers),
+                present(&right_layers),
+            ));
+        }
+
+        let (left_triggers, right_triggers) = (triggers(left), triggers(right));
+        for trigger in left_triggers.union(&right_triggers) {
+            let present = |triggers: &BTreeSet<String>| {
+                if triggers.contains(trigger) {
+                    trigger.clone()
+                } else {
+                    "".to_string()
+                }
+            };
+            rows.push(DiffRow::new(
+                "Trigger",
+                present(&left_triggers),
+                present(&right_triggers),
+            ));
+        }
+
+        let keys = l
+            .environment
+            .iter()
+            .chain(r.environment.iter())
+            .map(|(key, _)| key.clone())
+            .collect::<BTreeSet<_>>();
+
+        for key in keys {
+            let value = |environment: &[(String, String)]| {
+                environment
+                    .iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| v.clone())
+            };
+            let (left_value, right_value) = (value(&l.environment), value(&r.environment));
+
+            // masked values still need to show whether they match
+            let show = |value: &Option<String>, other: &Option<String>| match value {
+                Some(value) if self.revealed.contains(&key) => value.clone(),
+                Some(_) if value == other => MASK.to_string(),
+                Some(_) => format!("{} (differs)", MASK),
+                None => "".to_string(),
+            };
+
+            rows.push(DiffRow {
+                name: format!("${}", key),
+                left: show(&left_value, &right_value),
+                right: show(&right_value, &left_value),
+                differs: left_value != right_value,
+                variable: Some(key.clone()),
+            });
+        }
+
+        if self.only_differences {
+            rows.retain(|row| row.differs);
+        }
+
+        rows
+    }
+
+    fn toggle_reveal(&mut self) {
+        if let Some(key) = self
+            .rows()
+            .into_iter()
+            .nth(self.selected)
+            .and_then(|row| row.variable)
+        {
+            if !self.revealed.remove(&key) {
+                self.revealed.insert(key);
+            }
+        }
+    }
+}
+
+impl Component for ConfigDiffPage {
+    fn new(state: &State, action_tx: &tokio::sync::mpsc::UnboundedSender<Action>) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            action_tx: action_tx.clone(),
+            props: props_from_state(state),
+            selected: 0,
+            only_differences: false,
+            revealed: HashSet::new(),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Configuration Diff"
+    }
+
+    fn move_with_state(self, state: &State) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            action_tx: self.action_tx,
+            props: props_from_state(state),
+            selected: 0,
+            only_differences: self.only_differences,
+            revealed: HashSet::new(),
+        }
+    }
+
+    fn handle_key_event(&mut self, key: crossterm::event::KeyEvent) {
+        match key.code {
+            crossterm::event::KeyCode::Char('q') => {
+                self.action_tx.send(Action::Quit).unwrap();
+            }
+            // versions of one function go back to its dashboard, two functions back to search
+            crossterm::event::KeyCode::Esc => match (&self.props.left, &self.props.right) {
+                (Some(left), Some(right))
+                    if left.function.lambda.name == right.function.lambda.name =>
+                {
+                    self.action_tx
+                        .send(Action::PerformSearch {
+                            lambda: left.function.lambda.clone(),
+                        })
+                        .unwrap();
+                }
+                _ => {
+                    self.action_tx.send(Action::Search).unwrap();
+                }
+            },
+            crossterm::event::KeyCode::Char('j') | crossterm::event::KeyCode::Down => {
+                self.selected = (self.selected + 1).min(self.rows().len().saturating_sub(1));
+            }
+            crossterm::event::KeyCode::Char('k') | crossterm::event::KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            crossterm::event::KeyCode::Char('d') => {
+                self.only_differences = !self.only_differences;
+                self.selected = 0;
+            }
+            crossterm::event::KeyCode::Char('v') => self.toggle_reveal(),
+            _ => {}
+        }
+    }
+}
+
+impl ComponentRender<()> for ConfigDiffPage {
+    fn render(&self, frame: &mut Frame, _: ()) {
+        let chunks = Layout::default()
+            .constraints(
+                [
+                    Constraint::Length(1),
+                    Constraint::Min(1),
+                    Constraint::Length(1),
+                ]
+                .as_ref(),
+            )
+            .direction(Direction::Vertical)
+            .split(frame.size());
+
+        let name = |snapshot: &Option<FunctionSnapshot>| {
+            snapshot
+                .as_ref()
+                .map(|s| s.function.name())
+                .unwrap_or_default()
+        };
+        let (left, right) = (name(&self.props.left), name(&self.props.right));
+
+        frame.render_widget(
+            Text::styled(
+                format!("{} <-> {}", left, right),
+                Style::default().fg(Color::White).bold().bg(Color::DarkGray),
+            ),
+            chunks[0],
+        );
+
+        let rows = self
+            .rows()
+            .into_iter()
+            .enumerate()
+            .map(|(index, row)| {
+                let style = match (index == self.selected, row.differs) {
+                    (true, _) => Style::default().bg(Color::DarkGray).fg(Color::Yellow),
+                    (false, true) => Style::default().fg(Color::Yellow),
+                    (false, false) => Style::default(),
+                };
+                Row::new(vec![row.name, row.left, row.right]).style(style)
+            })
+            .collect::<Vec<_>>();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(25),
+                Constraint::Percentage(40),
+                Constraint::Min(1),
+            ],
+        )
+        .column_spacing(1)
+        .header(
+            Row::new(vec!["Setting".to_string(), left, right])
+                .underlined()
+                .bold(),
+        )
+        .block(
+            Block::default()
+                .title(if self.only_differences {
+                    "Differences"
+                } else {
+                    "Configuration"
+                })
+                .borders(Borders::ALL),
+        );
+
+        frame.render_widget(table, chunks[1]);
+
+        let help_text = Text::styled(
+            "help: [q] quit, [esc] back, [j/k] select, [d] only differences, [v] reveal value",
+            Style::default().fg(Color::White).bg(Color::DarkGray),
+        );
+
+        frame.render_widget(help_text, chunks[2]);
+    }
+}
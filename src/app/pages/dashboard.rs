@@ -1,15 +1,20 @@
-use crate::core::{DashboardState, EventSourceMapping, Lambda, Metric, State};
+use crate::core::{
+    Alias, AsyncConfig, Baseline, Concurrency, DeadLetterQueue, EventSourceMapping, Lambda, Metric,
+    QualifiedLambda, QueueHealth, State, Version,
+};
 use ratatui::{
-    layout::{Constraint, Layout},
+    layout::{Constraint, Layout, Rect},
     style::{Color, Style, Stylize},
-    symbols,
     text::Text,
-    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Row, Table},
+    widgets::{Block, Borders, Clear, Paragraph, Row, Table, Tabs, Wrap},
     Frame,
 };
 
 use crate::{
-    app::component::{Component, ComponentRender},
+    app::{
+        chart::{render_chart, render_charts, Overlay, ReferenceLine},
+        component::{Component, ComponentRender, ConfirmDialog, InputBox, RenderProps},
+    },
     core::Action,
 };
 
@@ -17,11 +22,92 @@ pub struct DashboardProps {
     lambda: Option<Lambda>,
     event_source_mappings: Vec<EventSourceMapping>,
     data: Option<Vec<Metric>>,
+    async_config: AsyncConfig,
+    async_metrics: Vec<Metric>,
+    async_error: Option<String>,
+    queues: Vec<QueueHealth>,
+    concurrency: Concurrency,
+    concurrency_error: Option<String>,
+    versions: Vec<Version>,
+    aliases: Vec<Alias>,
+    versions_error: Option<String>,
+    baseline: Option<Baseline>,
+    baseline_metrics: Vec<Metric>,
+    status: Option<String>,
+    starred: bool,
+}
+
+/// The concurrency setting currently being typed into the input box.
+#[derive(Debug, Clone)]
+pub enum ConcurrencyEdit {
+    Reserved,
+    Provisioned { qualifier: String },
+}
+
+/// A concurrency change waiting for confirmation.
+#[derive(Debug, Clone)]
+pub enum ConcurrencyChange {
+    Reserved {
+        old: Option<i64>,
+        new: Option<i64>,
+    },
+    Provisioned {
+        qualifier: String,
+        old: i64,
+        new: i64,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DashboardTab {
+    Metrics,
+    Async,
+    Concurrency,
+    Versions,
+}
+
+impl DashboardTab {
+    const ALL: [DashboardTab; 4] = [
+        DashboardTab::Metrics,
+        DashboardTab::Async,
+        DashboardTab::Concurrency,
+        DashboardTab::Versions,
+    ];
+
+    fn title(&self) -> &str {
+        match self {
+            DashboardTab::Metrics => "Metrics",
+            DashboardTab::Async => "Async",
+            DashboardTab::Concurrency => "Concurrency",
+            DashboardTab::Versions => "Versions",
+        }
+    }
+
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|tab| tab == self).unwrap()
+    }
+
+    fn next(&self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    fn previous(&self) -> Self {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
 }
 
 pub struct DashboardPage {
     action_tx: tokio::sync::mpsc::UnboundedSender<Action>,
     props: DashboardProps,
+    tab: DashboardTab,
+    selected_provisioned: usize,
+    /// index into the aliases followed by the versions
+    selected_version: usize,
+    /// an alias or version marked to have its configuration compared with another
+    marked_version: Option<String>,
+    input_box: InputBox,
+    editing: Option<ConcurrencyEdit>,
+    pending: Option<ConcurrencyChange>,
 }
 
 impl Component for DashboardPage {
@@ -38,7 +124,27 @@ impl Component for DashboardPage {
                 data: None,
                 lambda: None,
                 event_source_mappings: vec![],
+                async_config: AsyncConfig::default(),
+                async_metrics: vec![],
+                async_error: None,
+                queues: vec![],
+                concurrency: Concurrency::default(),
+                concurrency_error: None,
+                versions: vec![],
+                aliases: vec![],
+                versions_error: None,
+                baseline: None,
+                baseline_metrics: vec![],
+                status: None,
+                starred: false,
             },
+            tab: DashboardTab::Metrics,
+            selected_provisioned: 0,
+            selected_version: 0,
+            marked_version: None,
+            input_box: InputBox::new(state, action_tx),
+            editing: None,
+            pending: None,
         }
     }
 
@@ -57,18 +163,162 @@ impl Component for DashboardPage {
                     data: Some(dashboard.metrics.clone()),
                     lambda: Some(dashboard.lambda.clone()),
                     event_source_mappings: dashboard.event_source_mappings.clone(),
+                    async_config: dashboard.async_config.clone(),
+                    async_metrics: dashboard.async_metrics.clone(),
+                    async_error: dashboard.async_error.clone(),
+                    queues: dashboard.queues.clone(),
+                    concurrency: dashboard.concurrency.clone(),
+                    concurrency_error: dashboard.concurrency_error.clone(),
+                    versions: dashboard.versions.clone(),
+                    aliases: dashboard.aliases.clone(),
+                    versions_error: dashboard.versions_error.clone(),
+                    baseline: dashboard.baseline,
+                    baseline_metrics: dashboard.baseline_metrics.clone(),
+                    status: dashboard.status.clone(),
+                    starred: dashboard.starred,
                 }
             } else {
                 DashboardProps {
                     data: None,
                     lambda: None,
                     event_source_mappings: vec![],
+                    async_config: AsyncConfig::default(),
+                    async_metrics: vec![],
+                    async_error: None,
+                    queues: vec![],
+                    concurrency: Concurrency::default(),
+                    concurrency_error: None,
+                    versions: vec![],
+                    aliases: vec![],
+                    versions_error: None,
+                    baseline: None,
+                    baseline_metrics: vec![],
+                    status: None,
+                    starred: false,
                 }
             },
+            tab: self.tab,
+            selected_provisioned: self.selected_provisioned,
+            selected_version: self.selected_version,
+            marked_version: self.marked_version,
+            input_box: self.input_box,
+            editing: None,
+            pending: None,
         }
     }
 
     fn handle_key_event(&mut self, key: crossterm::event::KeyEvent) {
+        if self.pending.is_some() {
+            match key.code {
+                crossterm::event::KeyCode::Char('y') => self.apply_concurrency_change(),
+                crossterm::event::KeyCode::Char('n') | crossterm::event::KeyCode::Esc => {
+                    self.pending = None;
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        if self.editing.is_some() {
+            match key.code {
+                crossterm::event::KeyCode::Enter => self.submit_concurrency_edit(),
+                crossterm::event::KeyCode::Esc => {
+                    self.editing = None;
+                }
+                _ => self.input_box.handle_key_event(key),
+            }
+            return;
+        }
+
+        // nothing can be edited when the current concurrency couldn't be loaded
+        if self.tab == DashboardTab::Concurrency && self.props.concurrency_error.is_none() {
+            match key.code {
+                crossterm::event::KeyCode::Char('e') => {
+                    self.input_box.set_text(
+                        &self
+                            .props
+                            .concurrency
+                            .reserved
+                            .map(|x| x.to_string())
+                            .unwrap_or_default(),
+                    );
+                    self.editing = Some(ConcurrencyEdit::Reserved);
+                    return;
+                }
+                // the kill switch, throttle every invocation of the function
+                crossterm::event::KeyCode::Char('x') => {
+                    self.pending = Some(ConcurrencyChange::Reserved {
+                        old: self.props.concurrency.reserved,
+                        new: Some(0),
+                    });
+                    return;
+                }
+                crossterm::event::KeyCode::Char('p') => {
+                    if let Some(qualifier) =
+                        self.provisioned_qualifiers().get(self.selected_provisioned)
+                    {
+                        self.input_box
+                            .set_text(&self.requested_provisioned(qualifier).to_string());
+                        self.editing = Some(ConcurrencyEdit::Provisioned {
+                            qualifier: qualifier.clone(),
+                        });
+                    }
+                    return;
+                }
+                crossterm::event::KeyCode::Char('j') => {
+                    self.selected_provisioned = (self.selected_provisioned + 1)
+                        .min(self.provisioned_qualifiers().len().saturating_sub(1));
+                    return;
+                }
+                crossterm::event::KeyCode::Char('k') => {
+                    self.selected_provisioned = self.selected_provisioned.saturating_sub(1);
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        if self.tab == DashboardTab::Versions {
+            let count = self.props.aliases.len() + self.props.versions.len();
+            match key.code {
+                crossterm::event::KeyCode::Char('j') => {
+                    self.selected_version =
+                        (self.selected_version + 1).min(count.saturating_sub(1));
+                    return;
+                }
+                crossterm::event::KeyCode::Char('k') => {
+                    self.selected_version = self.selected_version.saturating_sub(1);
+                    return;
+                }
+                crossterm::event::KeyCode::Char('m') => {
+                    self.mark_version();
+                    return;
+                }
+                crossterm::event::KeyCode::Enter => {
+                    if let Some(lambda) = &self.props.lambda {
+                        let alias = self.props.aliases.get(self.selected_version).cloned();
+                        let version = self
+                            .selected_version
+                            .checked_sub(self.props.aliases.len())
+                            .and_then(|index| self.props.versions.get(index))
+                            .cloned();
+                        if alias.is_some() || version.is_some() {
+                            self.action_tx
+                                .send(Action::ShowVersion {
+                                    lambda: lambda.clone(),
+                                    alias,
+                                    version,
+                                    versions: self.props.versions.clone(),
+                                })
+                                .unwrap();
+                        }
+                    }
+                    return;
+                }
+                _ => {}
+            }
+        }
+
         match key.code {
             crossterm::event::KeyCode::Char('q') => {
                 self.action_tx.send(Action::Quit).unwrap();
@@ -76,65 +326,163 @@ impl Component for DashboardPage {
             crossterm::event::KeyCode::Char('s') => {
                 self.action_tx.send(Action::Search).unwrap();
             }
+            crossterm::event::KeyCode::Char('*') => {
+                if let Some(lambda) = &self.props.lambda {
+                    self.props.starred = !self.props.starred;
+                    self.action_tx
+                        .send(Action::ToggleFavourite {
+                            lambda: lambda.clone(),
+                        })
+                        .unwrap();
+                }
+            }
+            // cycle between no baseline, yesterday and last week
+            crossterm::event::KeyCode::Char('w') if self.tab == DashboardTab::Metrics => {
+                if let Some(lambda) = &self.props.lambda {
+                    let baseline = match self.props.baseline {
+                        None => Some(Baseline::DayAgo),
+                        Some(Baseline::DayAgo) => Some(Baseline::WeekAgo),
+                        Some(Baseline::WeekAgo) => None,
+                    };
+                    self.action_tx
+                        .send(Action::ShowBaseline {
+                            lambda: lambda.clone(),
+                            baseline,
+                        })
+                        .unwrap();
+                }
+            }
+            crossterm::event::KeyCode::Char('c') => {
+                if let Some(lambda) = &self.props.lambda {
+                    self.action_tx
+                        .send(Action::EditConfiguration {
+                            lambda: lambda.clone(),
+                        })
+                        .unwrap();
+                }
+            }
+            crossterm::event::KeyCode::Char('d') => {
+                let queues = self.dead_letter_queues();
+                if let (Some(lambda), false) = (&self.props.lambda, queues.is_empty()) {
+                    // open the first dead-letter queue which has messages waiting
+                    let selected = self
+                        .props
+                        .queues
+                        .iter()
+                        .filter(|q| q.redrive_policy.is_some())
+                        .position(|q| q.dead_letter_depth.unwrap_or(0) > 0)
+                        .unwrap_or(0);
+                    self.action_tx
+                        .send(Action::PeekDeadLetterQueue {
+                            lambda: lambda.clone(),
+                            queues,
+                            selected,
+                        })
+                        .unwrap();
+                }
+            }
+            crossterm::event::KeyCode::Tab => {
+                self.tab = self.tab.next();
+            }
+            crossterm::event::KeyCode::BackTab => {
+                self.tab = self.tab.previous();
+            }
             _ => {}
         }
     }
 }
 
-impl ComponentRender<()> for DashboardPage {
-    fn render(&self, frame: &mut Frame, _: ()) {
-        let chunks = Layout::default()
-            .constraints(
-                [
-                    Constraint::Length(1),
-                    Constraint::Length(if self.props.event_source_mappings.is_empty() {
-                        0
-                    } else {
-                        self.props.event_source_mappings.len() as u16 + 3
-                    }),
-                    Constraint::Min(1),
-                    Constraint::Length(1),
-                ]
-                .as_ref(),
-            )
-            .direction(ratatui::layout::Direction::Vertical)
-            .split(frame.size());
+fn render_queue(frame: &mut Frame, area: Rect, queue: &QueueHealth) {
+    let block = Block::default()
+        .title(format!("Queue: {}", queue.name))
+        .borders(Borders::ALL);
 
-        let lambda = self.props.lambda.as_ref().unwrap();
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
 
-        let lambda_detail_chunks = Layout::default()
-            .constraints(
-                [
-                    Constraint::Min(1),
-                    Constraint::Length(lambda.runtime.len() as u16 + 2),
-                    Constraint::Length(lambda.memory.to_string().len() as u16 + 2),
-                ]
-                .as_ref(),
+    let chunks = Layout::default()
+        .constraints([Constraint::Length(1), Constraint::Min(1)].as_ref())
+        .direction(ratatui::layout::Direction::Vertical)
+        .split(inner);
+
+    let dead_letter = match (&queue.redrive_policy, queue.dead_letter_depth) {
+        (Some(policy), depth) => {
+            let name = policy
+                .dead_letter_target_arn
+                .rsplit(':')
+                .next()
+                .unwrap_or_default();
+            let style = match depth {
+                Some(depth) if depth > 0 => Style::default().fg(Color::Black).bg(Color::Red),
+                _ => Style::default().fg(Color::White).bg(Color::DarkGray),
+            };
+            Text::styled(
+                format!(
+                    " DLQ: {} (max receives {}), depth: {} ",
+                    name,
+                    policy.max_receive_count,
+                    depth.map(|d| d.to_string()).unwrap_or("?".to_string())
+                ),
+                style,
             )
-            .direction(ratatui::layout::Direction::Horizontal)
-            .split(chunks[0]);
+        }
+        (None, _) => Text::styled(
+            " no redrive policy ",
+            Style::default().fg(Color::White).bg(Color::DarkGray),
+        ),
+    };
 
-        let lambda_name = Text::styled(
-            format!("{}", lambda.name),
-            Style::default().fg(Color::White).bold().bg(Color::DarkGray),
-        );
+    frame.render_widget(dead_letter, chunks[0]);
 
-        frame.render_widget(lambda_name, lambda_detail_chunks[0]);
+    match &queue.metrics_error {
+        Some(error) => frame.render_widget(
+            Paragraph::new(Text::styled(
+                format!("queue health unavailable: {}", error),
+                Style::default().red(),
+            ))
+            .wrap(Wrap { trim: false }),
+            chunks[1],
+        ),
+        None => render_charts(frame, chunks[1], &queue.metrics, None),
+    }
+}
 
-        let lambda_runtime = Text::styled(
-            format!(" {} ", lambda.runtime),
-            Style::default().fg(Color::White).bold().bg(Color::DarkGray),
-        );
+// total of a metric over the whole window, used to flag when events are being lost
+fn metric_total(metrics: &[Metric], name: &str) -> Option<f64> {
+    metrics
+        .iter()
+        .find(|m| m.name == name)
+        .map(|m| m.values.iter().sum())
+}
 
-        frame.render_widget(lambda_runtime, lambda_detail_chunks[1]);
+impl DashboardPage {
+    // the redrive targets of sqs triggers, followed by the function's own dlq if it is a queue
+    fn dead_letter_queues(&self) -> Vec<DeadLetterQueue> {
+        let mut queues: Vec<DeadLetterQueue> = self
+            .props
+            .queues
+            .iter()
+            .filter_map(|queue| {
+                queue.redrive_policy.as_ref().map(|policy| DeadLetterQueue {
+                    arn: policy.dead_letter_target_arn.clone(),
+                    source_arn: Some(queue.arn.clone()),
+                })
+            })
+            .collect();
 
-        let lambda_memory = Text::styled(
-            format!(" {} ", lambda.memory),
-            Style::default().fg(Color::White).bold().bg(Color::DarkGray),
-        );
+        if let Some(target) = &self.props.async_config.dead_letter_target {
+            if target.starts_with("arn:aws:sqs:") {
+                queues.push(DeadLetterQueue {
+                    arn: target.clone(),
+                    source_arn: None,
+                });
+            }
+        }
 
-        frame.render_widget(lambda_memory, lambda_detail_chunks[2]);
+        queues
+    }
 
+    fn render_event_source_mappings(&self, frame: &mut Frame, area: Rect) {
         let rows = self
             .props
             .event_source_mappings
@@ -205,120 +553,737 @@ impl ComponentRender<()> for DashboardPage {
                     .borders(Borders::ALL),
             );
 
-        frame.render_widget(table, chunks[1]);
+        frame.render_widget(table, area);
+    }
 
-        let help_text = Text::styled(
-            "help: [q] quit, [s] to search",
-            Style::default().fg(Color::White).bg(Color::DarkGray),
-        );
+    fn render_metrics_tab(&self, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .constraints(
+                [
+                    Constraint::Length(if self.props.event_source_mappings.is_empty() {
+                        0
+                    } else {
+                        self.props.event_source_mappings.len() as u16 + 3
+                    }),
+                    Constraint::Min(1),
+                ]
+                .as_ref(),
+            )
+            .direction(ratatui::layout::Direction::Vertical)
+            .split(area);
 
-        frame.render_widget(help_text, chunks[3]);
-
-        // favor width, count of 6 charts = 2x3, count of 5 = 2x2 + 1, count of 4 = 2x2, count of 3 = 1x3, count of 2 = 1x2, count of 1 = 1x1
-        let (x, y) = match &self.props.data {
-            Some(data) => {
-                let count = data.len();
-                match count {
-                    1 => (1, 1),
-                    2 => (1, 2),
-                    3 => (1, 3),
-                    4 => (2, 2),
-                    5 => (2, 2),
-                    _ => (2, 3),
-                }
-            }
-            None => (1, 1),
-        };
+        self.render_event_source_mappings(frame, chunks[0]);
+
+        // queue health is charted next to the function so we can see if the consumer keeps up
+        let chart_chunks = Layout::default()
+            .constraints(if self.props.queues.is_empty() {
+                [Constraint::Percentage(100), Constraint::Percentage(0)]
+            } else {
+                [Constraint::Percentage(50), Constraint::Percentage(50)]
+            })
+            .direction(ratatui::layout::Direction::Horizontal)
+            .split(chunks[1]);
 
-        let vertical_chart_chunks = Layout::default()
+        if let Some(data) = &self.props.data {
+            let overlay = self.props.baseline.map(|baseline| Overlay {
+                label: baseline.label(),
+                metrics: &self.props.baseline_metrics,
+            });
+            render_charts(frame, chart_chunks[0], data, overlay.as_ref());
+        }
+
+        let queue_chunks = Layout::default()
             .constraints(
-                (0..x)
-                    .map(|_| Constraint::Percentage(100 / x as u16))
+                self.props
+                    .queues
+                    .iter()
+                    .map(|_| Constraint::Ratio(1, self.props.queues.len() as u32))
                     .collect::<Vec<_>>(),
             )
-            .direction(ratatui::layout::Direction::Horizontal)
-            .split(chunks[2]);
+            .direction(ratatui::layout::Direction::Vertical)
+            .split(chart_chunks[1]);
 
-        let horizontal_chart_chunks: Vec<_> = vertical_chart_chunks
+        self.props
+            .queues
             .iter()
-            .map(|chunk| {
-                Layout::default()
-                    .constraints(
-                        (0..y)
-                            .map(|_| Constraint::Percentage(100 / y as u16))
-                            .collect::<Vec<_>>(),
+            .zip(queue_chunks.iter())
+            .for_each(|(queue, chunk)| render_queue(frame, *chunk, queue));
+    }
+
+    fn render_async_tab(&self, frame: &mut Frame, area: Rect) {
+        if let Some(error) = &self.props.async_error {
+            render_unavailable(frame, area, "Asynchronous Invocation", error);
+            return;
+        }
+
+        let chunks = Layout::default()
+            .constraints([Constraint::Length(10), Constraint::Min(1)].as_ref())
+            .direction(ratatui::layout::Direction::Vertical)
+            .split(area);
+
+        let config = &self.props.async_config;
+        let metrics = &self.props.async_metrics;
+
+        let metric_cell = |label: &str, name: &str| match metric_total(metrics, name) {
+            Some(total) if total > 0.0 => {
+                Text::styled(format!("{}: {}", label, total), Style::default().red())
+            }
+            Some(total) => Text::styled(format!("{}: {}", label, total), Style::default().green()),
+            None => Text::raw(""),
+        };
+
+        let rows = vec![
+            Row::new(vec![
+                Text::raw("Maximum Retry Attempts"),
+                Text::raw(
+                    config
+                        .maximum_retry_attempts
+                        .map(|x| x.to_string())
+                        .unwrap_or("2 (default)".to_string()),
+                ),
+                Text::raw(""),
+            ]),
+            Row::new(vec![
+                Text::raw("Maximum Event Age"),
+                Text::raw(
+                    config
+                        .maximum_event_age_in_seconds
+                        .map(|x| format!("{}s", x))
+                        .unwrap_or("21600s (default)".to_string()),
+                ),
+                Text::raw(""),
+            ]),
+            Row::new(vec![
+                Text::raw("On Success"),
+                Text::raw(config.on_success.clone().unwrap_or("-".to_string())),
+                Text::raw(""),
+            ]),
+            Row::new(vec![
+                Text::raw("On Failure"),
+                Text::raw(config.on_failure.clone().unwrap_or("-".to_string())),
+                Text::raw(""),
+            ]),
+            // the metric doesn't say which destination failed, so it is shown once for both
+            Row::new(vec![
+                Text::raw("Destination Delivery"),
+                Text::raw(""),
+                if config.on_success.is_some() || config.on_failure.is_some() {
+                    metric_cell(
+                        "DestinationDeliveryFailures",
+                        "destination_delivery_failures",
                     )
-                    .direction(ratatui::layout::Direction::Vertical)
-                    .split(*chunk)
-            })
-            .collect();
+                } else {
+                    Text::raw("")
+                },
+            ]),
+            Row::new(vec![
+                Text::raw("Dead Letter Queue"),
+                Text::raw(config.dead_letter_target.clone().unwrap_or("-".to_string())),
+                if config.dead_letter_target.is_some() {
+                    metric_cell("DeadLetterErrors", "dead_letter_errors")
+                } else {
+                    Text::raw("")
+                },
+            ]),
+            Row::new(vec![
+                Text::raw("Dropped Events"),
+                Text::raw(""),
+                metric_cell("AsyncEventsDropped", "async_events_dropped"),
+            ]),
+        ];
 
-        if let Some(data) = &self.props.data {
-            data.iter().enumerate().for_each(|(index, data)| {
-                let dataset: Vec<_> = data
-                    .timestamps
-                    .iter()
-                    .zip(data.values.iter().clone())
-                    .map(|(timestamp, value)| (*timestamp as f64, *value as f64))
-                    .clone()
-                    .collect();
+        let widths = vec![
+            Constraint::Length(24),
+            Constraint::Min(1),
+            Constraint::Length(36),
+        ];
 
-                let (min_x, max_x) = dataset
-                    .iter()
-                    .fold((f64::MAX, f64::MIN), |(min, max), (x, _)| {
-                        (min.min(*x), max.max(*x))
-                    });
+        let table = Table::new(rows, widths)
+            .column_spacing(1)
+            .header(
+                Row::new(vec!["Setting", "Value", "Metric (24h)"])
+                    .underlined()
+                    .bold(),
+            )
+            .block(
+                Block::default()
+                    .title("Asynchronous Invocation")
+                    .borders(Borders::ALL),
+            );
+
+        frame.render_widget(table, chunks[0]);
 
-                let (min_y, max_y) = dataset
+        render_charts(frame, chunks[1], metrics, None);
+    }
+}
+
+impl DashboardPage {
+    // the qualifiers with provisioned concurrency, followed by the aliases without any so that
+    // it can be added to them
+    fn provisioned_qualifiers(&self) -> Vec<String> {
+        let provisioned = &self.props.concurrency.provisioned;
+        provisioned
+            .iter()
+            .map(|p| p.qualifier.clone())
+            .chain(
+                self.props
+                    .aliases
                     .iter()
-                    .fold((f64::MAX, f64::MIN), |(min, max), (_, y)| {
-                        (min.min(*y), max.max(*y))
-                    });
+                    .filter(|alias| !provisioned.iter().any(|p| p.qualifier == alias.name))
+                    .map(|alias| alias.name.clone()),
+            )
+            .collect()
+    }
 
-                let line_style = match data.name.as_str() {
-                    "errors" => Style::default().red(),
-                    _ => Style::default().green(),
-                };
-
-                let datasets = vec![Dataset::default()
-                    .name(data.name.clone())
-                    .marker(symbols::Marker::Braille)
-                    .graph_type(GraphType::Line)
-                    .style(line_style)
-                    .data(&dataset)];
-
-                // Create the X axis and define its properties
-                let x_axis = Axis::default()
-                    .title("Time".white())
-                    .style(Style::default().gray())
-                    .bounds([min_x, max_x])
-                    .labels(vec!["A".into(), "B".into(), "C".into()]);
-
-                // Create the Y axis and define its properties
-                let y_axis = Axis::default()
-                    .title("Count".white())
-                    .style(Style::default().gray())
-                    .bounds([0.0, max_y])
-                    .labels(vec![
-                        min_y.to_string().into(),
-                        (min_y + (max_y - min_y) / 2.0).to_string().into(),
-                        max_y.to_string().into(),
-                    ]);
-
-                // Create the chart and link all the parts together
-                let chart = Chart::new(datasets)
-                    .block(
-                        Block::default()
-                            .title(data.name.to_string())
-                            .borders(Borders::ALL),
-                    )
-                    .x_axis(x_axis)
-                    .y_axis(y_axis);
+    fn requested_provisioned(&self, qualifier: &str) -> i64 {
+        self.props
+            .concurrency
+            .provisioned
+            .iter()
+            .find(|p| p.qualifier == qualifier)
+            .map(|p| p.requested)
+            .unwrap_or(0)
+    }
 
-                let chunk = horizontal_chart_chunks[index / y][index % y];
+    // the alias name or version number of the selected row on the versions tab
+    fn selected_qualifier(&self) -> Option<String> {
+        match self.props.aliases.get(self.selected_version) {
+            Some(alias) => Some(alias.name.clone()),
+            None => self
+                .selected_version
+                .checked_sub(self.props.aliases.len())
+                .and_then(|index| self.props.versions.get(index))
+                .map(|version| version.version.clone()),
+        }
+    }
 
-                frame.render_widget(chart, chunk);
+    // mark the selected alias or version, marking a second one compares the two
+    fn mark_version(&mut self) {
+        let (lambda, selected) = match (&self.props.lambda, self.selected_qualifier()) {
+            (Some(lambda), Some(selected)) => (lambda.clone(), selected),
+            _ => return,
+        };
+
+        match self.marked_version.take() {
+            Some(marked) if marked == selected => {}
+            Some(marked) => {
+                self.action_tx
+                    .send(Action::CompareConfiguration {
+                        left: QualifiedLambda {
+                            lambda: lambda.clone(),
+                            qualifier: Some(marked),
+                        },
+                        right: QualifiedLambda {
+                            lambda,
+                            qualifier: Some(selected),
+                        },
+                        // the concurrency is only reused if it could be loaded
+                        loaded: self.props.concurrency_error.is_none().then(|| {
+                            (
+                                self.props.concurrency.clone(),
+                                self.props.event_source_mappings.clone(),
+                            )
+                        }),
+                    })
+                    .unwrap();
+            }
+            None => self.marked_version = Some(selected),
+        }
+    }
+
+    fn submit_concurrency_edit(&mut self) {
+        let text = self.input_box.text().trim().to_string();
+
+        let change = match &self.editing {
+            // an empty reserved concurrency removes the reservation
+            Some(ConcurrencyEdit::Reserved) if text.is_empty() => {
+                Some(ConcurrencyChange::Reserved {
+                    old: self.props.concurrency.reserved,
+                    new: None,
+                })
+            }
+            Some(ConcurrencyEdit::Reserved) => {
+                text.parse::<i32>().ok().filter(|x| *x >= 0).map(|new| {
+                    ConcurrencyChange::Reserved {
+                        old: self.props.concurrency.reserved,
+                        new: Some(new.into()),
+                    }
+                })
+            }
+            Some(ConcurrencyEdit::Provisioned { qualifier }) => {
+                text.parse::<i32>().ok().filter(|x| *x >= 0).map(|new| {
+                    ConcurrencyChange::Provisioned {
+                        qualifier: qualifier.clone(),
+                        old: self.requested_provisioned(qualifier),
+                        new: new.into(),
+                    }
+                })
+            }
+            None => None,
+        };
+
+        // keep the input open until a valid number is entered
+        if change.is_some() {
+            self.editing = None;
+            self.pending = change;
+        }
+    }
+
+    fn apply_concurrency_change(&mut self) {
+        let lambda = match &self.props.lambda {
+            Some(lambda) => lambda.clone(),
+            None => return,
+        };
+
+        match self.pending.take() {
+            Some(ConcurrencyChange::Reserved { new, .. }) => {
+                self.action_tx
+                    .send(Action::SetReservedConcurrency {
+                        lambda,
+                        reserved: new,
+                    })
+                    .unwrap();
+            }
+            Some(ConcurrencyChange::Provisioned { qualifier, new, .. }) => {
+                self.action_tx
+                    .send(Action::SetProvisionedConcurrency {
+                        lambda,
+                        qualifier,
+                        provisioned: new,
+                    })
+                    .unwrap();
+            }
+            None => {}
+        }
+    }
+
+    fn concurrency_confirm_dialog(&self) -> Option<ConfirmDialog> {
+        let format_reserved =
+            |x: &Option<i64>| x.map(|x| x.to_string()).unwrap_or("none".to_string());
+
+        match self.pending.as_ref()? {
+            ConcurrencyChange::Reserved { old, new } => {
+                let mut lines = vec![format!(
+                    "Reserved concurrency: {} -> {}",
+                    format_reserved(old),
+                    format_reserved(new)
+                )];
+                if *new == Some(0) {
+                    lines.push("Every invocation of the function will be throttled!".to_string());
+                }
+                Some(ConfirmDialog {
+                    title: "Set Reserved Concurrency".to_string(),
+                    lines,
+                })
+            }
+            ConcurrencyChange::Provisioned {
+                qualifier,
+                old,
+                new,
+            } => Some(ConfirmDialog {
+                title: "Set Provisioned Concurrency".to_string(),
+                lines: vec![format!(
+                    "Provisioned concurrency on {}: {} -> {}",
+                    qualifier, old, new
+                )],
+            }),
+        }
+    }
+
+    fn render_concurrency_input(&self, frame: &mut Frame) {
+        let title = match &self.editing {
+            Some(ConcurrencyEdit::Reserved) => "Reserved concurrency (empty to remove)".to_string(),
+            Some(ConcurrencyEdit::Provisioned { qualifier }) => {
+                format!("Provisioned concurrency on {} (0 to remove)", qualifier)
+            }
+            None => return,
+        };
+
+        let area = frame.size();
+        let width = (title.len() as u16 + 4).min(area.width);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + area.height.saturating_sub(3) / 2,
+            width,
+            3.min(area.height),
+        );
+
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .fg(Color::Yellow);
+        let inner = block.inner(popup);
+
+        frame.render_widget(Clear, popup);
+        frame.render_widget(block, popup);
+
+        self.input_box.render(
+            frame,
+            RenderProps {
+                title: "Concurrency".to_string(),
+                area: inner,
+                border_color: Color::Yellow,
+                show_cursor: true,
+            },
+        );
+    }
+
+    fn render_concurrency_tab(&self, frame: &mut Frame, area: Rect) {
+        if let Some(error) = &self.props.concurrency_error {
+            render_unavailable(frame, area, "Concurrency", error);
+            return;
+        }
+
+        let concurrency = &self.props.concurrency;
+        let qualifiers = self.provisioned_qualifiers();
+
+        let chunks = Layout::default()
+            .constraints(
+                [
+                    Constraint::Length(qualifiers.len() as u16 + 6),
+                    Constraint::Min(1),
+                ]
+                .as_ref(),
+            )
+            .direction(ratatui::layout::Direction::Vertical)
+            .split(area);
+
+        let mut rows = vec![
+            Row::new(vec![
+                "Reserved".to_string(),
+                concurrency
+                    .reserved
+                    .map(|x| x.to_string())
+                    .unwrap_or("-".to_string()),
+                "".to_string(),
+            ]),
+            Row::new(vec![
+                "Account Limit".to_string(),
+                concurrency.account_limit.to_string(),
+                "".to_string(),
+            ]),
+            Row::new(vec![
+                "Unreserved Capacity".to_string(),
+                concurrency.unreserved.to_string(),
+                "".to_string(),
+            ]),
+        ];
+
+        rows.extend(qualifiers.iter().enumerate().map(|(index, qualifier)| {
+            let row = match concurrency
+                .provisioned
+                .iter()
+                .find(|p| &p.qualifier == qualifier)
+            {
+                Some(provisioned) => Row::new(vec![
+                    format!("Provisioned ({})", provisioned.qualifier),
+                    format!(
+                        "{}/{} allocated, {} available",
+                        provisioned.allocated, provisioned.requested, provisioned.available
+                    ),
+                    match &provisioned.status_reason {
+                        Some(reason) => format!("{}: {}", provisioned.status, reason),
+                        None => provisioned.status.clone(),
+                    },
+                ]),
+                None => Row::new(vec![
+                    format!("Provisioned ({})", qualifier),
+                    "-".to_string(),
+                    "".to_string(),
+                ]),
+            };
+            if index == self.selected_provisioned {
+                row.style(Style::default().bg(Color::DarkGray).fg(Color::Yellow))
+            } else {
+                row
+            }
+        }));
+
+        let widths = vec![
+            Constraint::Length(30),
+            Constraint::Length(30),
+            Constraint::Min(1),
+        ];
+
+        let table = Table::new(rows, widths)
+            .column_spacing(1)
+            .header(
+                Row::new(vec!["Limit", "Value", "Status"])
+                    .underlined()
+                    .bold(),
+            )
+            .block(Block::default().title("Concurrency").borders(Borders::ALL));
+
+        frame.render_widget(table, chunks[0]);
+
+        // the ceiling is the reserved concurrency, or whatever is left unreserved in the account
+        let mut references = vec![match concurrency.reserved {
+            Some(reserved) => ReferenceLine {
+                name: "reserved".to_string(),
+                value: reserved as f64,
+                color: Color::Red,
+            },
+            None => ReferenceLine {
+                name: "unreserved".to_string(),
+                value: concurrency.unreserved as f64,
+                color: Color::Yellow,
+            },
+        }];
+
+        let provisioned: i64 = concurrency.provisioned.iter().map(|p| p.allocated).sum();
+        if provisioned > 0 {
+            references.push(ReferenceLine {
+                name: "provisioned".to_string(),
+                value: provisioned as f64,
+                color: Color::Blue,
             });
         }
+
+        let concurrent_executions = self
+            .props
+            .data
+            .iter()
+            .flatten()
+            .find(|m| m.name == "concurrent_executions");
+
+        if let Some(concurrent_executions) = concurrent_executions {
+            render_chart(frame, chunks[1], concurrent_executions, None, &references);
+        }
     }
 }
+
+impl DashboardPage {
+    fn with_mark(&self, qualifier: &str) -> String {
+        if self.marked_version.as_deref() == Some(qualifier) {
+            format!("* {}", qualifier)
+        } else {
+            qualifier.to_string()
+        }
+    }
+
+    fn render_versions_tab(&self, frame: &mut Frame, area: Rect) {
+        if let Some(error) = &self.props.versions_error {
+            render_unavailable(frame, area, "Versions", error);
+            return;
+        }
+
+        let chunks = Layout::default()
+            .constraints(
+                [
+                    Constraint::Length(self.props.aliases.len() as u16 + 3),
+                    Constraint::Min(1),
+                ]
+                .as_ref(),
+            )
+            .direction(ratatui::layout::Direction::Vertical)
+            .split(area);
+
+        let highlight = |row: Row<'static>, index: usize| {
+            if index == self.selected_version {
+                row.style(Style::default().bg(Color::DarkGray).fg(Color::Yellow))
+            } else {
+                row
+            }
+        };
+
+        let alias_rows = self
+            .props
+            .aliases
+            .iter()
+            .enumerate()
+            .map(|(index, alias)| {
+                let routing = alias
+                    .traffic_split()
+                    .iter()
+                    .map(|(version, weight)| format!("{}: {:.0}%", version, weight * 100.0))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                highlight(
+                    Row::new(vec![
+                        self.with_mark(&alias.name),
+                        alias.function_version.clone(),
+                        routing,
+                        alias.description.clone(),
+                    ]),
+                    index,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let aliases = Table::new(
+            alias_rows,
+            [
+                Constraint::Length(20),
+                Constraint::Length(10),
+                Constraint::Length(30),
+                Constraint::Min(1),
+            ],
+        )
+        .column_spacing(1)
+        .header(
+            Row::new(vec!["Alias", "Version", "Routing", "Description"])
+                .underlined()
+                .bold(),
+        )
+        .block(Block::default().title("Aliases").borders(Borders::ALL));
+
+        frame.render_widget(aliases, chunks[0]);
+
+        let version_rows = self
+            .props
+            .versions
+            .iter()
+            .enumerate()
+            .map(|(index, version)| {
+                highlight(
+                    Row::new(vec![
+                        self.with_mark(&version.version),
+                        version.code_sha256.chars().take(12).collect::<String>(),
+                        version.last_modified.clone(),
+                        version.description.clone(),
+                    ]),
+                    index + self.props.aliases.len(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let versions = Table::new(
+            version_rows,
+            [
+                Constraint::Length(10),
+                Constraint::Length(14),
+                Constraint::Length(30),
+                Constraint::Min(1),
+            ],
+        )
+        .column_spacing(1)
+        .header(
+            Row::new(vec!["Version", "Code SHA", "Last Modified", "Description"])
+                .underlined()
+                .bold(),
+        )
+        .block(Block::default().title("Versions").borders(Borders::ALL));
+
+        frame.render_widget(versions, chunks[1]);
+    }
+}
+
+impl ComponentRender<()> for DashboardPage {
+    fn render(&self, frame: &mut Frame, _: ()) {
+        let chunks = Layout::default()
+            .constraints(
+                [
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                    Constraint::Min(1),
+                    Constraint::Length(if self.props.status.is_some() { 1 } else { 0 }),
+                    Constraint::Length(1),
+                ]
+                .as_ref(),
+            )
+            .direction(ratatui::layout::Direction::Vertical)
+            .split(frame.size());
+
+        let lambda = self.props.lambda.as_ref().unwrap();
+
+        let lambda_detail_chunks = Layout::default()
+            .constraints(
+                [
+                    Constraint::Min(1),
+                    Constraint::Length(lambda.runtime.len() as u16 + 2),
+                    Constraint::Length(lambda.memory.to_string().len() as u16 + 2),
+                ]
+                .as_ref(),
+            )
+            .direction(ratatui::layout::Direction::Horizontal)
+            .split(chunks[0]);
+
+        let lambda_name = Text::styled(
+            if self.props.starred {
+                format!("★ {}", lambda.name)
+            } else {
+                lambda.name.to_string()
+            },
+            Style::default().fg(Color::White).bold().bg(Color::DarkGray),
+        );
+
+        frame.render_widget(lambda_name, lambda_detail_chunks[0]);
+
+        let lambda_runtime = Text::styled(
+            format!(" {} ", lambda.runtime),
+            Style::default().fg(Color::White).bold().bg(Color::DarkGray),
+        );
+
+        frame.render_widget(lambda_runtime, lambda_detail_chunks[1]);
+
+        let lambda_memory = Text::styled(
+            format!(" {} ", lambda.memory),
+            Style::default().fg(Color::White).bold().bg(Color::DarkGray),
+        );
+
+        frame.render_widget(lambda_memory, lambda_detail_chunks[2]);
+
+        let tabs = Tabs::new(DashboardTab::ALL.iter().map(|tab| tab.title()))
+            .select(self.tab.index())
+            .highlight_style(Style::default().fg(Color::Yellow).bold());
+
+        frame.render_widget(tabs, chunks[1]);
+
+        match self.tab {
+            DashboardTab::Metrics => self.render_metrics_tab(frame, chunks[2]),
+            DashboardTab::Async => self.render_async_tab(frame, chunks[2]),
+            DashboardTab::Concurrency => self.render_concurrency_tab(frame, chunks[2]),
+            DashboardTab::Versions => self.render_versions_tab(frame, chunks[2]),
+        }
+
+        if let Some(status) = &self.props.status {
+            frame.render_widget(
+                Text::styled(status.as_str(), Style::default().yellow()),
+                chunks[3],
+            );
+        }
+
+        let help_text = match self.tab {
+            DashboardTab::Concurrency => Text::styled(
+                "help: [q] quit, [s] to search, [*] star, [tab] next tab, [e] edit reserved, [x] kill switch, [j/k] select, [p] edit provisioned",
+                Style::default().fg(Color::White).bg(Color::DarkGray),
+            ),
+            DashboardTab::Versions => Text::styled(
+                "help: [q] quit, [s] to search, [*] star, [tab] next tab, [j/k] select, [enter] version metrics, [m] mark to compare",
+                Style::default().fg(Color::White).bg(Color::DarkGray),
+            ),
+            DashboardTab::Metrics => Text::styled(
+                "help: [q] quit, [s] to search, [*] star, [tab] next tab, [c] configuration, [d] dead letters, [w] compare with yesterday/last week",
+                Style::default().fg(Color::White).bg(Color::DarkGray),
+            ),
+            _ => Text::styled(
+                "help: [q] quit, [s] to search, [*] star, [tab] next tab, [c] configuration, [d] dead letters",
+                Style::default().fg(Color::White).bg(Color::DarkGray),
+            ),
+        };
+
+        frame.render_widget(help_text, chunks[4]);
+
+        self.render_concurrency_input(frame);
+
+        if let Some(dialog) = self.concurrency_confirm_dialog() {
+            dialog.render(frame, ());
+        }
+    }
+}
+
+// a tab whose data couldn't be loaded shows why instead of an empty table
+fn render_unavailable(frame: &mut Frame, area: Rect, title: &str, error: &str) {
+    let paragraph = Paragraph::new(Text::styled(
+        format!("Unavailable: {}", error),
+        Style::default().red(),
+    ))
+    .wrap(Wrap { trim: false })
+    .block(
+        Block::default()
+            .title(title.to_string())
+            .borders(Borders::ALL),
+    );
+
+    frame.render_widget(paragraph, area);
+}
@@ -0,0 +1,383 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style, Stylize},
+    text::{Line, Text},
+    widgets::{Block, Borders, Paragraph, Row, Table, Wrap},
+    Frame,
+};
+
+use crate::{
+    app::component::{Component, ComponentRender, ConfirmDialog},
+    core::{Action, DeadLetterQueue, Lambda, QueueMessage, State},
+};
+
+pub struct DeadLetterProps {
+    lambda: Option<Lambda>,
+    queues: Vec<DeadLetterQueue>,
+    selected: usize,
+    messages: Vec<QueueMessage>,
+    status: Option<String>,
+}
+
+pub enum PendingAction {
+    Redrive,
+    Invoke { payload: String },
+}
+
+pub struct DeadLetterPage {
+    action_tx: tokio::sync::mpsc::UnboundedSender<Action>,
+    props: DeadLetterProps,
+    highlighted_index: usize,
+    pending: Option<PendingAction>,
+}
+
+fn props_from_state(state: &State) -> DeadLetterProps {
+    if let State::DeadLetter(dead_letter) = state {
+        DeadLetterProps {
+            lambda: Some(dead_letter.lambda.clone()),
+            queues: dead_letter.queues.clone(),
+            selected: dead_letter.selected,
+            messages: dead_letter.messages.clone(),
+            status: dead_letter.status.clone(),
+        }
+    } else {
+        DeadLetterProps {
+            lambda: None,
+            queues: vec![],
+            selected: 0,
+            messages: vec![],
+            status: None,
+        }
+    }
+}
+
+fn pretty_print(body: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(body)
+        .and_then(|value| serde_json::to_string_pretty(&value))
+        .unwrap_or(body.to_string())
+}
+
+// messages dead-lettered from an sqs event source were delivered to the function as a record
+// of an sqs event, so they are replayed in the same envelope
+fn sqs_event(message: &QueueMessage, source_arn: &str) -> String {
+    let attributes = message
+        .attributes
+        .iter()
+        .map(|(k, v)| (k.clone(), serde_json::json!(v)))
+        .collect::<serde_json::Map<_, _>>();
+
+    let message_attributes = message
+        .message_attributes
+        .iter()
+        .map(|(k, v)| {
+            (
+                k.clone(),
+                serde_json::json!({ "stringValue": v, "dataType": "String" }),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>();
+
+    serde_json::json!({
+        "Records": [{
+            "messageId": message.message_id,
+            "receiptHandle": "",
+            "body": message.body,
+            "attributes": attributes,
+            "messageAttributes": message_attributes,
+            "md5OfBody": "",
+            "eventSource": "aws:sqs",
+            "eventSourceARN": source_arn,
+            "awsRegion": source_arn.split(':').nth(3).unwrap_or_default(),
+        }]
+    })
+    .to_string()
+}
+
+impl DeadLetterPage {
+    fn queue(&self) -> Option<&DeadLetterQueue> {
+        self.props.queues.get(self.props.selected)
+    }
+
+    fn send_with_queue(
+        &self,
+        selected: usize,
+        action: impl Fn(Lambda, Vec<DeadLetterQueue>, usize) -> Action,
+    ) {
+        if let Some(lambda) = &self.props.lambda {
+            self.action_tx
+                .send(action(lambda.clone(), self.props.queues.clone(), selected))
+                .unwrap();
+        }
+    }
+
+    fn confirm(&mut self) {
+        match self.pending.take() {
+            Some(PendingAction::Redrive) => {
+                self.send_with_queue(self.props.selected, |lambda, queues, selected| {
+                    Action::RedriveDeadLetterQueue {
+                        lambda,
+                        queues,
+                        selected,
+                    }
+                });
+            }
+            Some(PendingAction::Invoke { payload }) => {
+                self.send_with_queue(self.props.selected, |lambda, queues, selected| {
+                    Action::InvokeWithMessage {
+                        lambda,
+                        queues,
+                        selected,
+                        payload: payload.clone(),
+                    }
+                });
+            }
+            None => {}
+        }
+    }
+
+    fn confirm_dialog(&self) -> Option<ConfirmDialog> {
+        let queue = self.queue()?;
+        let lambda = self.props.lambda.as_ref()?;
+
+        match self.pending.as_ref()? {
+            PendingAction::Redrive => Some(ConfirmDialog {
+                title: "Redrive".to_string(),
+                lines: vec![
+                    format!("Move all messages from {}", queue.name()),
+                    format!("back to {}?", queue.source_arn.clone().unwrap_or_default()),
+                ],
+            }),
+            PendingAction::Invoke { .. } => Some(ConfirmDialog {
+                title: "Invoke".to_string(),
+                lines: vec![format!(
+                    "Invoke {} with the selected message {}?",
+                    lambda.name,
+                    if queue.source_arn.is_some() {
+                        "as an SQS event"
+                    } else {
+                        "body"
+                    }
+                )],
+            }),
+        }
+    }
+}
+
+impl Component for DeadLetterPage {
+    fn new(state: &State, action_tx: &tokio::sync::mpsc::UnboundedSender<Action>) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            action_tx: action_tx.clone(),
+            props: props_from_state(state),
+            highlighted_index: 0,
+            pending: None,
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Dead Letters"
+    }
+
+    fn move_with_state(self, state: &State) -> Self
+    where
+        Self: Sized,
+    {
+        let props = props_from_state(state);
+        Self {
+            action_tx: self.action_tx,
+            highlighted_index: self
+                .highlighted_index
+                .min(props.messages.len().saturating_sub(1)),
+            props,
+            pending: None,
+        }
+    }
+
+    fn handle_key_event(&mut self, key: crossterm::event::KeyEvent) {
+        if self.pending.is_some() {
+            match key.code {
+                crossterm::event::KeyCode::Char('y') => self.confirm(),
+                crossterm::event::KeyCode::Char('n') | crossterm::event::KeyCode::Esc => {
+                    self.pending = None;
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        match key.code {
+            crossterm::event::KeyCode::Char('q') => {
+                self.action_tx.send(Action::Quit).unwrap();
+            }
+            crossterm::event::KeyCode::Esc => {
+                if let Some(lambda) = &self.props.lambda {
+                    self.action_tx
+                        .send(Action::PerformSearch {
+                            lambda: lambda.clone(),
+                        })
+                        .unwrap();
+                }
+            }
+            crossterm::event::KeyCode::Char('j') | crossterm::event::KeyCode::Down => {
+                self.highlighted_index =
+                    (self.highlighted_index + 1).min(self.props.messages.len().saturating_sub(1));
+            }
+            crossterm::event::KeyCode::Char('k') | crossterm::event::KeyCode::Up => {
+                self.highlighted_index = self.highlighted_index.saturating_sub(1);
+            }
+            crossterm::event::KeyCode::Char('p') => {
+                self.send_with_queue(self.props.selected, |lambda, queues, selected| {
+                    Action::PeekDeadLetterQueue {
+                        lambda,
+                        queues,
+                        selected,
+                    }
+                });
+            }
+            crossterm::event::KeyCode::Tab if !self.props.queues.is_empty() => {
+                let next = (self.props.selected + 1) % self.props.queues.len();
+                self.send_with_queue(next, |lambda, queues, selected| {
+                    Action::PeekDeadLetterQueue {
+                        lambda,
+                        queues,
+                        selected,
+                    }
+                });
+            }
+            crossterm::event::KeyCode::Char('r')
+                if self.queue().and_then(|q| q.source_arn.as_ref()).is_some() =>
+            {
+                self.pending = Some(PendingAction::Redrive);
+            }
+            crossterm::event::KeyCode::Char('i') => {
+                if let Some(message) = self.props.messages.get(self.highlighted_index) {
+                    let payload = match self.queue().and_then(|q| q.source_arn.as_ref()) {
+                        Some(source_arn) => sqs_event(message, source_arn),
+                        None => message.body.clone(),
+                    };
+                    self.pending = Some(PendingAction::Invoke { payload });
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl ComponentRender<()> for DeadLetterPage {
+    fn render(&self, frame: &mut Frame, _: ()) {
+        let chunks = Layout::default()
+            .constraints(
+                [
+                    Constraint::Length(1),
+                    Constraint::Min(1),
+                    Constraint::Length(if self.props.status.is_some() { 1 } else { 0 }),
+                    Constraint::Length(1),
+                ]
+                .as_ref(),
+            )
+            .direction(Direction::Vertical)
+            .split(frame.size());
+
+        let title = match (&self.props.lambda, self.queue()) {
+            (Some(lambda), Some(queue)) => format!(
+                "{} - {} ({}/{})",
+                lambda.name,
+                queue.name(),
+                self.props.selected + 1,
+                self.props.queues.len()
+            ),
+            (Some(lambda), None) => format!("{} - no dead-letter queues", lambda.name),
+            _ => "".to_string(),
+        };
+
+        frame.render_widget(
+            Text::styled(
+                title,
+                Style::default().fg(Color::White).bold().bg(Color::DarkGray),
+            ),
+            chunks[0],
+        );
+
+        let body_chunks = Layout::default()
+            .constraints([Constraint::Percentage(30), Constraint::Percentage(70)].as_ref())
+            .direction(Direction::Horizontal)
+            .split(chunks[1]);
+
+        let rows = self
+            .props
+            .messages
+            .iter()
+            .enumerate()
+            .map(|(index, message)| {
+                let row = Row::new(vec![message.message_id.clone()]);
+                if index == self.highlighted_index {
+                    row.style(Style::default().bg(Color::DarkGray).fg(Color::Yellow))
+                } else {
+                    row
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let table = Table::new(rows, [Constraint::Min(1)]).block(
+            Block::default()
+                .title(format!("Messages ({})", self.props.messages.len()))
+                .borders(Borders::ALL),
+        );
+
+        frame.render_widget(table, body_chunks[0]);
+
+        let mut lines: Vec<Line> = Vec::new();
+        if let Some(message) = self.props.messages.get(self.highlighted_index) {
+            lines.push(Line::from("Body".bold().underlined()));
+            lines.extend(
+                pretty_print(&message.body)
+                    .lines()
+                    .map(|l| Line::from(l.to_string())),
+            );
+            lines.push(Line::from(""));
+            lines.push(Line::from("Attributes".bold().underlined()));
+            lines.extend(
+                message
+                    .attributes
+                    .iter()
+                    .map(|(k, v)| Line::from(format!("{}: {}", k, v))),
+            );
+            if !message.message_attributes.is_empty() {
+                lines.push(Line::from(""));
+                lines.push(Line::from("Message Attributes".bold().underlined()));
+                lines.extend(
+                    message
+                        .message_attributes
+                        .iter()
+                        .map(|(k, v)| Line::from(format!("{}: {}", k, v))),
+                );
+            }
+        }
+
+        let message = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().title("Message").borders(Borders::ALL));
+
+        frame.render_widget(message, body_chunks[1]);
+
+        if let Some(status) = &self.props.status {
+            frame.render_widget(
+                Text::styled(status.as_str(), Style::default().yellow()),
+                chunks[2],
+            );
+        }
+
+        let help_text = Text::styled(
+            "help: [q] quit, [esc] dashboard, [j/k] select, [tab] next queue, [p] peek again, [r] redrive, [i] invoke with message",
+            Style::default().fg(Color::White).bg(Color::DarkGray),
+        );
+
+        frame.render_widget(help_text, chunks[3]);
+
+        if let Some(dialog) = self.confirm_dialog() {
+            dialog.render(frame, ());
+        }
+    }
+}
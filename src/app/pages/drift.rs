@@ -0,0 +1,293 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Text,
+    widgets::{Block, Borders, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::{
+    app::component::{Component, ComponentRender},
+    core::{Action, DriftGroup, State},
+};
+
+pub struct DriftProps {
+    groups: Vec<DriftGroup>,
+    grouped_by: String,
+    status: Option<String>,
+}
+
+pub struct DriftPage {
+    action_tx: tokio::sync::mpsc::UnboundedSender<Action>,
+    props: DriftProps,
+    selected: usize,
+    only_drifting: bool,
+}
+
+fn props_from_state(state: &State) -> DriftProps {
+    if let State::Drift(drift) = state {
+        DriftProps {
+            groups: drift.groups.clone(),
+            grouped_by: drift.grouped_by.clone(),
+            status: drift.status.clone(),
+        }
+    } else {
+        DriftProps {
+            groups: vec![],
+            grouped_by: "".to_string(),
+            status: None,
+        }
+    }
+}
+
+impl DriftPage {
+    fn visible_groups(&self) -> Vec<&DriftGroup> {
+        self.props
+            .groups
+            .iter()
+            .filter(|g| !self.only_drifting || g.needs_attention())
+            .collect()
+    }
+
+    fn render_groups(&self, frame: &mut Frame, area: Rect) {
+        let rows = self
+            .visible_groups()
+            .into_iter()
+            .enumerate()
+            .map(|(index, group)| {
+                let drifting = group.settings.iter().filter(|s| s.differs()).count();
+                let row = Row::new(vec![
+                    group.name.clone(),
+                    group.stages.len().to_string(),
+                    if group.errors.is_empty() {
+                        drifting.to_string()
+                    } else {
+                        format!("{}?", drifting)
+                    },
+                ]);
+                match (
+                    index == self.selected,
+                    drifting > 0,
+                    group.errors.is_empty(),
+                ) {
+                    (true, _, _) => {
+                        row.style(Style::default().bg(Color::DarkGray).fg(Color::Yellow))
+                    }
+                    (false, _, false) => row.style(Style::default().fg(Color::Red)),
+                    (false, true, true) => row.style(Style::default().fg(Color::Yellow)),
+                    (false, false, true) => row,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(1),
+                Constraint::Length(6),
+                Constraint::Length(6),
+            ],
+        )
+        .column_spacing(1)
+        .header(
+            Row::new(vec!["Function", "Stages", "Drift"])
+                .underlined()
+                .bold(),
+        )
+        .block(Block::default().title("Functions").borders(Borders::ALL));
+
+        frame.render_widget(table, area);
+    }
+
+    fn render_settings(&self, frame: &mut Frame, area: Rect) {
+        let group = match self.visible_groups().get(self.selected) {
+            Some(group) => (*group).clone(),
+            None => {
+                frame.render_widget(
+                    Block::default().title("Settings").borders(Borders::ALL),
+                    area,
+                );
+                return;
+            }
+        };
+
+        let rows = group
+            .settings
+            .iter()
+            .map(|setting| {
+                let mut cells = vec![setting.name.clone()];
+                cells.extend(setting.values.iter().cloned());
+                let row = Row::new(cells);
+                if setting.differs() {
+                    row.style(Style::default().fg(Color::Yellow))
+                } else {
+                    row
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut widths = vec![Constraint::Length(18)];
+        widths.extend(
+            group
+                .stages
+                .iter()
+                .map(|_| Constraint::Ratio(1, group.stages.len() as u32)),
+        );
+
+        let mut header = vec!["Setting".to_string()];
+        header.extend(group.stages.iter().cloned());
+
+        let table = Table::new(rows, widths)
+            .column_spacing(1)
+            .header(Row::new(header).underlined().bold())
+            .block(
+                Block::default()
+                    .title(group.functions.join(", "))
+                    .borders(Borders::ALL),
+            );
+
+        // the functions left out of the comparison are listed under it
+        let chunks = Layout::default()
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(if group.errors.is_empty() {
+                    0
+                } else {
+                    group.errors.len() as u16 + 2
+                }),
+            ])
+            .direction(Direction::Vertical)
+            .split(area);
+
+        frame.render_widget(table, chunks[0]);
+
+        let errors = Paragraph::new(group.errors.join("\n"))
+            .style(Style::default().fg(Color::Red))
+            .block(
+                Block::default()
+                    .title("Could not be compared")
+                    .borders(Borders::ALL),
+            );
+
+        frame.render_widget(errors, chunks[1]);
+    }
+}
+
+impl Component for DriftPage {
+    fn new(state: &State, action_tx: &tokio::sync::mpsc::UnboundedSender<Action>) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            action_tx: action_tx.clone(),
+            props: props_from_state(state),
+            selected: 0,
+            only_drifting: true,
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Drift"
+    }
+
+    fn move_with_state(self, state: &State) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            action_tx: self.action_tx,
+            props: props_from_state(state),
+            selected: self.selected,
+            only_drifting: self.only_drifting,
+        }
+    }
+
+    fn handle_key_event(&mut self, key: crossterm::event::KeyEvent) {
+        match key.code {
+            crossterm::event::KeyCode::Char('q') => {
+                self.action_tx.send(Action::Quit).unwrap();
+            }
+            crossterm::event::KeyCode::Esc => {
+                self.action_tx.send(Action::Search).unwrap();
+            }
+            crossterm::event::KeyCode::Char('j') | crossterm::event::KeyCode::Down => {
+                self.selected =
+                    (self.selected + 1).min(self.visible_groups().len().saturating_sub(1));
+            }
+            crossterm::event::KeyCode::Char('k') | crossterm::event::KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            crossterm::event::KeyCode::Char('d') => {
+                self.only_drifting = !self.only_drifting;
+                self.selected = 0;
+            }
+            crossterm::event::KeyCode::Char('e') => {
+                self.action_tx
+                    .send(Action::ExportDriftReport {
+                        groups: self.props.groups.clone(),
+                        grouped_by: self.props.grouped_by.clone(),
+                    })
+                    .unwrap();
+            }
+            _ => {}
+        }
+    }
+}
+
+impl ComponentRender<()> for DriftPage {
+    fn render(&self, frame: &mut Frame, _: ()) {
+        let chunks = Layout::default()
+            .constraints(
+                [
+                    Constraint::Length(1),
+                    Constraint::Min(1),
+                    Constraint::Length(if self.props.status.is_some() { 1 } else { 0 }),
+                    Constraint::Length(1),
+                ]
+                .as_ref(),
+            )
+            .direction(Direction::Vertical)
+            .split(frame.size());
+
+        let drifting = self.props.groups.iter().filter(|g| g.drifts()).count();
+
+        frame.render_widget(
+            Text::styled(
+                format!(
+                    "Drift report, grouped by {}: {} of {} functions drift between stages",
+                    self.props.grouped_by,
+                    drifting,
+                    self.props.groups.len()
+                ),
+                Style::default().fg(Color::White).bold().bg(Color::DarkGray),
+            ),
+            chunks[0],
+        );
+
+        let body_chunks = Layout::default()
+            .constraints([Constraint::Percentage(35), Constraint::Percentage(65)].as_ref())
+            .direction(Direction::Horizontal)
+            .split(chunks[1]);
+
+        self.render_groups(frame, body_chunks[0]);
+        self.render_settings(frame, body_chunks[1]);
+
+        if let Some(status) = &self.props.status {
+            frame.render_widget(
+                Text::styled(status.as_str(), Style::default().yellow()),
+                chunks[2],
+            );
+        }
+
+        let help_text = Text::styled(
+            if self.only_drifting {
+                "help: [q] quit, [esc] search, [j/k] select, [d] show all functions, [e] export markdown"
+            } else {
+                "help: [q] quit, [esc] search, [j/k] select, [d] only drifting functions, [e] export markdown"
+            },
+            Style::default().fg(Color::White).bg(Color::DarkGray),
+        );
+
+        frame.render_widget(help_text, chunks[3]);
+    }
+}
@@ -0,0 +1,268 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style, Stylize},
+    text::Text,
+    widgets::{Block, Borders, Row, Table},
+    Frame,
+};
+
+use crate::{
+    app::component::{Component, ComponentRender},
+    core::{Action, FleetMetrics, FleetWindow, State},
+};
+
+pub struct FleetProps {
+    window: FleetWindow,
+    functions: Vec<FleetMetrics>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SortColumn {
+    Errors,
+    ErrorRate,
+    Throttles,
+    Duration,
+    Invocations,
+}
+
+impl SortColumn {
+    fn next(&self) -> Self {
+        match self {
+            SortColumn::Errors => SortColumn::ErrorRate,
+            SortColumn::ErrorRate => SortColumn::Throttles,
+            SortColumn::Throttles => SortColumn::Duration,
+            SortColumn::Duration => SortColumn::Invocations,
+            SortColumn::Invocations => SortColumn::Errors,
+        }
+    }
+
+    fn value(&self, metrics: &FleetMetrics) -> f64 {
+        match self {
+            SortColumn::Errors => metrics.errors,
+            SortColumn::ErrorRate => metrics.error_rate(),
+            SortColumn::Throttles => metrics.throttles,
+            SortColumn::Duration => metrics.p99_duration,
+            SortColumn::Invocations => metrics.invocations,
+        }
+    }
+}
+
+const COLUMNS: [(&str, SortColumn); 5] = [
+    ("Errors", SortColumn::Errors),
+    ("Error %", SortColumn::ErrorRate),
+    ("Throttles", SortColumn::Throttles),
+    ("p99 (ms)", SortColumn::Duration),
+    ("Invocations", SortColumn::Invocations),
+];
+
+pub struct FleetPage {
+    action_tx: tokio::sync::mpsc::UnboundedSender<Action>,
+    props: FleetProps,
+    selected: usize,
+    sort: SortColumn,
+    ascending: bool,
+}
+
+fn props_from_state(state: &State) -> FleetProps {
+    if let State::Fleet(fleet) = state {
+        FleetProps {
+            window: fleet.window,
+            functions: fleet.functions.clone(),
+        }
+    } else {
+        FleetProps {
+            window: FleetWindow::Day,
+            functions: vec![],
+        }
+    }
+}
+
+impl FleetPage {
+    fn sorted(&self) -> Vec<&FleetMetrics> {
+        let mut functions = self.props.functions.iter().collect::<Vec<_>>();
+        functions.sort_by(|a, b| {
+            let ordering = self.sort.value(a).total_cmp(&self.sort.value(b));
+            if self.ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+        functions
+    }
+}
+
+impl Component for FleetPage {
+    fn new(state: &State, action_tx: &tokio::sync::mpsc::UnboundedSender<Action>) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            action_tx: action_tx.clone(),
+            props: props_from_state(state),
+            selected: 0,
+            sort: SortColumn::Errors,
+            ascending: false,
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Fleet"
+    }
+
+    fn move_with_state(self, state: &State) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            action_tx: self.action_tx,
+            props: props_from_state(state),
+            selected: 0,
+            sort: self.sort,
+            ascending: self.ascending,
+        }
+    }
+
+    fn handle_key_event(&mut self, key: crossterm::event::KeyEvent) {
+        match key.code {
+            crossterm::event::KeyCode::Char('q') => {
+                self.action_tx.send(Action::Quit).unwrap();
+            }
+            crossterm::event::KeyCode::Esc => {
+                self.action_tx.send(Action::Search).unwrap();
+            }
+            crossterm::event::KeyCode::Char('j') | crossterm::event::KeyCode::Down => {
+                self.selected =
+                    (self.selected + 1).min(self.props.functions.len().saturating_sub(1));
+            }
+            crossterm::event::KeyCode::Char('k') | crossterm::event::KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            crossterm::event::KeyCode::Char('s') | crossterm::event::KeyCode::Tab => {
+                self.sort = self.sort.next();
+                self.selected = 0;
+            }
+            crossterm::event::KeyCode::Char('o') => {
+                self.ascending = !self.ascending;
+                self.selected = 0;
+            }
+            crossterm::event::KeyCode::Char('w') => {
+                self.action_tx
+                    .send(Action::Fleet {
+                        window: self.props.window.next(),
+                    })
+                    .unwrap();
+            }
+            crossterm::event::KeyCode::Char('r') => {
+                self.action_tx
+                    .send(Action::Fleet {
+                        window: self.props.window,
+                    })
+                    .unwrap();
+            }
+            crossterm::event::KeyCode::Enter => {
+                if let Some(metrics) = self.sorted().get(self.selected) {
+                    self.action_tx
+                        .send(Action::PerformSearch {
+                            lambda: metrics.lambda.clone(),
+                        })
+                        .unwrap();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl ComponentRender<()> for FleetPage {
+    fn render(&self, frame: &mut Frame, _: ()) {
+        let chunks = Layout::default()
+            .constraints(
+                [
+                    Constraint::Length(1),
+                    Constraint::Min(1),
+                    Constraint::Length(1),
+                ]
+                .as_ref(),
+            )
+            .direction(Direction::Vertical)
+            .split(frame.size());
+
+        let failing = self
+            .props
+            .functions
+            .iter()
+            .filter(|f| f.errors > 0.0 || f.throttles > 0.0)
+            .count();
+
+        frame.render_widget(
+            Text::styled(
+                format!(
+                    "Fleet overview, last {}: {} of {} functions with errors or throttles",
+                    self.props.window.label(),
+                    failing,
+                    self.props.functions.len()
+                ),
+                Style::default().fg(Color::White).bold().bg(Color::DarkGray),
+            ),
+            chunks[0],
+        );
+
+        let rows = self
+            .sorted()
+            .into_iter()
+            .enumerate()
+            .map(|(index, metrics)| {
+                let row = Row::new(vec![
+                    metrics.lambda.name.clone(),
+                    format!("{:.0}", metrics.errors),
+                    format!("{:.2}", metrics.error_rate()),
+                    format!("{:.0}", metrics.throttles),
+                    format!("{:.0}", metrics.p99_duration),
+                    format!("{:.0}", metrics.invocations),
+                ]);
+                match (
+                    index == self.selected,
+                    metrics.errors > 0.0 || metrics.throttles > 0.0,
+                ) {
+                    (true, _) => row.style(Style::default().bg(Color::DarkGray).fg(Color::Yellow)),
+                    (false, true) => row.style(Style::default().fg(Color::Red)),
+                    (false, false) => row,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut header = vec!["Function".to_string()];
+        header.extend(COLUMNS.iter().map(|(name, column)| {
+            match (*column == self.sort, self.ascending) {
+                (true, true) => format!("{} ^", name),
+                (true, false) => format!("{} v", name),
+                (false, _) => name.to_string(),
+            }
+        }));
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(1),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(11),
+                Constraint::Length(10),
+                Constraint::Length(13),
+            ],
+        )
+        .column_spacing(1)
+        .header(Row::new(header).underlined().bold())
+        .block(Block::default().title("Functions").borders(Borders::ALL));
+
+        frame.render_widget(table, chunks[1]);
+
+        let help_text = Text::styled(
+            "help: [q] quit, [esc] search, [j/k] select, [enter] dashboard, [s] sort column, [o] sort order, [w] window, [r] refresh",
+            Style::default().fg(Color::White).bg(Color::DarkGray),
+        );
+
+        frame.render_widget(help_text, chunks[2]);
+    }
+}
@@ -1,18 +1,36 @@
 use ratatui::Frame;
 
-use self::{dashboard::DashboardPage, error::ErrorPage, search::SearchPage, splash::SplashPage};
+use self::{
+    compare::ComparePage, config::ConfigPage, config_diff::ConfigDiffPage,
+    dashboard::DashboardPage, dead_letter::DeadLetterPage, drift::DriftPage, error::ErrorPage,
+    fleet::FleetPage, search::SearchPage, splash::SplashPage, version::VersionPage,
+};
 
 use super::component::{Component, ComponentRender};
 
+mod compare;
+mod config;
+mod config_diff;
 mod dashboard;
+mod dead_letter;
+mod drift;
 mod error;
+mod fleet;
 mod search;
 mod splash;
+mod version;
 
 pub enum Page {
     Splash,
     Search,
     Dashboard,
+    DeadLetter,
+    Version,
+    Config,
+    ConfigDiff,
+    Drift,
+    Compare,
+    Fleet,
     Error,
 }
 
@@ -21,6 +39,13 @@ pub struct AppRouter {
     pub splash: SplashPage,
     pub search: SearchPage,
     pub dashboard: DashboardPage,
+    pub dead_letter: DeadLetterPage,
+    pub version: VersionPage,
+    pub config: ConfigPage,
+    pub config_diff: ConfigDiffPage,
+    pub drift: DriftPage,
+    pub compare: ComparePage,
+    pub fleet: FleetPage,
     pub error: ErrorPage,
 }
 
@@ -30,6 +55,13 @@ impl AppRouter {
             Page::Splash => &mut self.splash,
             Page::Search => &mut self.search,
             Page::Dashboard => &mut self.dashboard,
+            Page::DeadLetter => &mut self.dead_letter,
+            Page::Version => &mut self.version,
+            Page::Config => &mut self.config,
+            Page::ConfigDiff => &mut self.config_diff,
+            Page::Drift => &mut self.drift,
+            Page::Compare => &mut self.compare,
+            Page::Fleet => &mut self.fleet,
             Page::Error => &mut self.error,
         }
     }
@@ -39,6 +71,13 @@ impl AppRouter {
             Page::Splash => &self.splash,
             Page::Search => &self.search,
             Page::Dashboard => &self.dashboard,
+            Page::DeadLetter => &self.dead_letter,
+            Page::Version => &self.version,
+            Page::Config => &self.config,
+            Page::ConfigDiff => &self.config_diff,
+            Page::Drift => &self.drift,
+            Page::Compare => &self.compare,
+            Page::Fleet => &self.fleet,
             Page::Error => &self.error,
         }
     }
@@ -57,6 +96,13 @@ impl Component for AppRouter {
             splash: SplashPage::new(state, action_tx),
             search: SearchPage::new(state, action_tx),
             dashboard: DashboardPage::new(state, action_tx),
+            dead_letter: DeadLetterPage::new(state, action_tx),
+            version: VersionPage::new(state, action_tx),
+            config: ConfigPage::new(state, action_tx),
+            config_diff: ConfigDiffPage::new(state, action_tx),
+            drift: DriftPage::new(state, action_tx),
+            compare: ComparePage::new(state, action_tx),
+            fleet: FleetPage::new(state, action_tx),
             error: ErrorPage::new(state, action_tx),
         }
     }
@@ -65,6 +111,10 @@ impl Component for AppRouter {
         self.get_current_page_mut().handle_key_event(key);
     }
 
+    fn handle_tick(&mut self, area: ratatui::layout::Rect) {
+        self.get_current_page_mut().handle_tick(area);
+    }
+
     fn name(&self) -> &str {
         self.get_current_page().name()
     }
@@ -78,12 +128,26 @@ impl Component for AppRouter {
                 crate::core::State::Search(_) => Page::Search,
                 crate::core::State::Searching(_) => Page::Search,
                 crate::core::State::Dashboard(_) => Page::Dashboard,
+                crate::core::State::DeadLetter(_) => Page::DeadLetter,
+                crate::core::State::Version(_) => Page::Version,
+                crate::core::State::Config(_) => Page::Config,
+                crate::core::State::ConfigDiff(_) => Page::ConfigDiff,
+                crate::core::State::Drift(_) => Page::Drift,
+                crate::core::State::Compare(_) => Page::Compare,
+                crate::core::State::Fleet(_) => Page::Fleet,
                 crate::core::State::Error(_) => Page::Error,
                 _ => Page::Splash,
             },
             splash: self.splash.move_with_state(state),
             search: self.search.move_with_state(state),
             dashboard: self.dashboard.move_with_state(state),
+            dead_letter: self.dead_letter.move_with_state(state),
+            version: self.version.move_with_state(state),
+            config: self.config.move_with_state(state),
+            config_diff: self.config_diff.move_with_state(state),
+            drift: self.drift.move_with_state(state),
+            compare: self.compare.move_with_state(state),
+            fleet: self.fleet.move_with_state(state),
             error: self.error.move_with_state(state),
         }
     }
@@ -95,6 +159,13 @@ impl ComponentRender<()> for AppRouter {
             Page::Splash => self.splash.render(frame, props),
             Page::Search => self.search.render(frame, props),
             Page::Dashboard => self.dashboard.render(frame, props),
+            Page::DeadLetter => self.dead_letter.render(frame, props),
+            Page::Version => self.version.render(frame, props),
+            Page::Config => self.config.render(frame, props),
+            Page::ConfigDiff => self.config_diff.render(frame, props),
+            Page::Drift => self.drift.render(frame, props),
+            Page::Compare => self.compare.render(frame, props),
+            Page::Fleet => self.fleet.render(frame, props),
             Page::Error => self.error.render(frame, props),
         }
     }
@@ -0,0 +1,181 @@
+use ratatui::{
+    layout::Constraint,
+    style::{Color, Style, Stylize},
+    widgets::{Block, Borders, Clear, List, ListItem},
+    Frame,
+};
+
+use crate::{
+    app::component::centered_area,
+    config::{Column, SortOrder},
+    core::{Action, Lambda},
+    query::SearchScope,
+};
+
+use super::SearchPage;
+
+impl SearchPage {
+    // keep the column layout, and search again in the new order
+    pub(super) fn save_columns(&mut self) {
+        self.action_tx
+            .send(Action::SaveSearchConfig {
+                config: self.columns.clone(),
+            })
+            .unwrap();
+        self.searched = None;
+        self.apply_query();
+    }
+
+    // rank by match, then sort by name and each shown column in turn
+    pub(super) fn cycle_sort(&mut self) {
+        let mut columns = vec![Column::Name];
+        columns.extend(self.columns.columns.iter().copied());
+
+        let next = match self.columns.sort {
+            None => columns.first(),
+            Some(sort) => columns.iter().skip_while(|c| **c != sort.column).nth(1),
+        };
+
+        self.columns.sort = next.map(|column| SortOrder {
+            column: *column,
+            descending: false,
+        });
+        self.save_columns();
+    }
+
+    pub(super) fn reverse_sort(&mut self) {
+        if let Some(sort) = &mut self.columns.sort {
+            sort.descending = !sort.descending;
+            self.save_columns();
+        }
+    }
+
+    pub(super) fn handle_column_picker_key(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        cursor: usize,
+    ) {
+        match key.code {
+            crossterm::event::KeyCode::Char('j') | crossterm::event::KeyCode::Down => {
+                self.column_picker = Some((cursor + 1).min(Column::OPTIONAL.len() - 1));
+            }
+            crossterm::event::KeyCode::Char('k') | crossterm::event::KeyCode::Up => {
+                self.column_picker = Some(cursor.saturating_sub(1));
+            }
+            crossterm::event::KeyCode::Char(' ') => {
+                let column = Column::OPTIONAL[cursor];
+                if self.columns.columns.contains(&column) {
+                    self.columns.columns.retain(|c| *c != column);
+                    if self.columns.sort.map(|s| s.column) == Some(column) {
+                        self.columns.sort = None;
+                    }
+                } else {
+                    self.columns.columns.push(column);
+                }
+                self.save_columns();
+            }
+            crossterm::event::KeyCode::Esc
+            | crossterm::event::KeyCode::Enter
+            | crossterm::event::KeyCode::Char('o') => {
+                self.column_picker = None;
+            }
+            _ => {}
+        }
+    }
+
+    pub(super) fn render_column_picker(&self, frame: &mut Frame, cursor: usize) {
+        let area = centered_area(frame.size(), 40, Column::OPTIONAL.len() as u16 + 4);
+
+        let items = Column::OPTIONAL
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                let item = ListItem::new(format!(
+                    "[{}] {}",
+                    if self.columns.columns.contains(column) {
+                        "x"
+                    } else {
+                        " "
+                    },
+                    column.title()
+                ));
+                if index == cursor {
+                    item.style(Style::default().bg(Color::DarkGray).fg(Color::Yellow))
+                } else {
+                    item
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let list = List::new(items).block(
+            Block::default()
+                .title("Columns: [space] toggle, [esc] close")
+                .borders(Borders::ALL)
+                .fg(Color::Yellow),
+        );
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(list, area);
+    }
+
+    pub(super) fn column_width(&self, column: Column) -> Constraint {
+        let longest = |value: fn(&Lambda) -> usize| {
+            self.matcher.lambdas().iter().map(value).max().unwrap_or(0)
+        };
+
+        // the header is the minimum, long free text is cut off
+        let width = match column {
+            Column::Runtime => longest(|l| l.runtime.len()),
+            Column::Memory => longest(|l| l.memory.to_string().len()),
+            Column::Timeout => longest(|l| l.timeout.to_string().len()),
+            Column::Architecture => longest(|l| l.architecture.len()),
+            Column::LastModified => 16,
+            Column::CodeSize => 9,
+            Column::Name | Column::Description | Column::Tags => 30,
+        };
+
+        // room for the sort marker
+        Constraint::Length((width.max(column.title().len() + 2)) as u16)
+    }
+
+    // the widths and titles of the columns of the results, the sorted column marked
+    pub(super) fn header(&self) -> (Vec<Constraint>, Vec<String>) {
+        let mut widths = vec![Constraint::Min(1)];
+        if self.scope != SearchScope::Name {
+            widths.push(Constraint::Length(40));
+        }
+        widths.extend(
+            self.columns
+                .columns
+                .iter()
+                .map(|column| self.column_width(*column)),
+        );
+
+        let mut header = vec![Column::Name];
+        header.extend(self.columns.columns.iter().copied());
+        let mut header = header
+            .into_iter()
+            .map(|column| match self.columns.sort {
+                Some(sort) if sort.column == column && sort.descending => {
+                    format!("{} v", column.title())
+                }
+                Some(sort) if sort.column == column => format!("{} ^", column.title()),
+                _ => column.title().to_string(),
+            })
+            .collect::<Vec<_>>();
+        if self.scope != SearchScope::Name {
+            header.insert(1, "matched".to_string());
+        }
+
+        if self.show_activity {
+            widths.extend([
+                Constraint::Length(12),
+                Constraint::Length(6),
+                Constraint::Length(6),
+            ]);
+            header.extend(["activity", "errors", "health"].map(String::from));
+        }
+
+        (widths, header)
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use crate::{config::GroupBy, query::Match};
+
+use super::SearchPage;
+
+/// A row of the results, a function or, while grouped, the heading of a group.
+pub(super) enum Entry<'a> {
+    Group {
+        name: String,
+        functions: usize,
+        /// how many of the functions had errors recently, of those fetched
+        with_errors: usize,
+        collapsed: bool,
+    },
+    Function(&'a Match),
+}
+
+impl SearchPage {
+    // the rows of the results, the matches in order or, while grouped, the groups in order of
+    // name with the functions of those not collapsed under them
+    pub(super) fn entries<'a>(&self, matches: &'a [Match]) -> Vec<Entry<'a>> {
+        let group_by = match self.columns.group_by {
+            Some(group_by) => group_by,
+            None => return matches.iter().map(Entry::Function).collect(),
+        };
+        let tag = self.columns.group_tag.as_deref();
+
+        let mut groups: HashMap<Option<String>, Vec<&Match>> = HashMap::new();
+        for m in matches {
+            groups
+                .entry(group_by.group(self.matcher.lambda(m), tag))
+                .or_default()
+                .push(m);
+        }
+
+        // the functions without a group come last
+        let mut groups = groups.into_iter().collect::<Vec<_>>();
+        groups.sort_by(|(a, _), (b, _)| (a.is_none(), a).cmp(&(b.is_none(), b)));
+
+        let mut entries = vec![];
+        for (name, functions) in groups {
+            let name = name.unwrap_or_else(|| format!("(no {})", group_by.title(tag)));
+            let collapsed = self.collapsed.contains(&name);
+            let with_errors = functions
+                .iter()
+                .filter(|m| {
+                    self.props
+                        .activity
+                        .get(&self.matcher.lambda(m).name)
+                        .is_some_and(|activity| activity.errors > 0.0)
+                })
+                .count();
+
+            entries.push(Entry::Group {
+                name,
+                functions: functions.len(),
+                with_errors,
+                collapsed,
+            });
+            if !collapsed {
+                entries.extend(functions.into_iter().map(Entry::Function));
+            }
+        }
+        entries
+    }
+
+    pub(super) fn highlighted_entry<'a>(&self, entries: &'a [Entry<'a>]) -> Option<&'a Entry<'a>> {
+        entries.get(
+            self.props
+                .highlighted_index
+                .min(entries.len().saturating_sub(1)),
+        )
+    }
+
+    // fold the highlighted group away, or back out, returning whether a group was highlighted
+    pub(super) fn toggle_group(&mut self) -> bool {
+        let matches = self.matcher.matches();
+        let name = match self.highlighted_entry(&self.entries(&matches)) {
+            Some(Entry::Group { name, .. }) => name.clone(),
+            _ => return false,
+        };
+
+        if !self.collapsed.remove(&name) {
+            self.collapsed.insert(name);
+        }
+        true
+    }
+
+    pub(super) fn cycle_group_by(&mut self) {
+        self.columns.group_by =
+            GroupBy::next(self.columns.group_by, self.columns.group_tag.as_deref());
+        self.collapsed.clear();
+        self.select(0);
+        self.save_columns();
+    }
+}
@@ -0,0 +1,881 @@
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+    sync::Arc,
+    time::Instant,
+};
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{
+        Block, Borders, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table, TableState,
+    },
+    Frame,
+};
+
+use crate::{
+    app::component::{Component, ComponentRender, InputBox},
+    config::SearchConfig,
+    core::{Action, FleetWindow, Health, Lambda, Preview, QualifiedLambda, RecentActivity},
+    favourites::Favourites,
+    query::{self, Field, Matcher, Query, SearchScope},
+};
+
+use self::grouping::Entry;
+
+mod columns;
+mod grouping;
+mod preview;
+
+pub struct SearchProps {
+    highlighted_index: usize,
+    activity: HashMap<String, RecentActivity>,
+    preview: Option<Preview>,
+}
+
+pub struct SearchPage {
+    action_tx: tokio::sync::mpsc::UnboundedSender<Action>,
+    props: SearchProps,
+    /// scores the functions against the query away from the render path
+    matcher: Matcher,
+    /// the input the current results were searched with, none to search again
+    searched: Option<String>,
+    /// which attributes of the functions the query text is matched against
+    scope: SearchScope,
+    /// starred and recently viewed functions, shown first while there is no text
+    favourites: Favourites,
+    /// whether only the starred functions are shown
+    starred_only: bool,
+    /// the groups folded away while the results are grouped
+    collapsed: HashSet<String>,
+    /// the columns shown and the order of the results
+    columns: SearchConfig,
+    /// the position of the cursor in the column picker while it is open
+    column_picker: Option<usize>,
+    input_mode: InputMode,
+    input_box: InputBox,
+    /// a function marked to have its configuration compared with another
+    marked: Option<Lambda>,
+    /// whether the recent activity columns are shown
+    show_activity: bool,
+    /// functions whose recent activity has been asked for, rows are only fetched once they are
+    /// on screen
+    requested: HashSet<String>,
+    /// why the query in the input box could not be parsed, the previous results stay on screen
+    query_error: Option<String>,
+    /// previous queries, most recent first
+    history: Vec<String>,
+    history_index: Option<usize>,
+    /// the area the page was drawn in on the last tick, which decides how many results fit
+    area: Rect,
+    /// the first result on screen, and how many fit
+    offset: usize,
+    page_size: usize,
+    /// a `g` waiting for a second one to jump to the top
+    pending_g: bool,
+    /// the highlighted function and since when, its preview is asked for once it has rested
+    /// there for `PREVIEW_DEBOUNCE`, which is checked on every tick
+    preview_candidate: Option<(String, Instant)>,
+    preview_requested: Option<String>,
+    /// the function whose preview failed, fetched again once the selection comes back to it
+    preview_failed: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InputMode {
+    Normal,
+    Insert,
+}
+
+const SPARKLINE: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+fn sparkline(values: &[f64]) -> String {
+    let max = values.iter().fold(0.0, |max: f64, value| max.max(*value));
+    values
+        .iter()
+        .map(|value| match *value {
+            v if v <= 0.0 => ' ',
+            v => {
+                SPARKLINE[((v / max * SPARKLINE.len() as f64).ceil() as usize - 1)
+                    .min(SPARKLINE.len() - 1)]
+            }
+        })
+        .collect()
+}
+
+fn health_badge(health: Health) -> Span<'static> {
+    match health {
+        Health::Idle => "●".dark_gray(),
+        Health::Healthy => "●".green(),
+        Health::Degraded => "●".yellow(),
+        Health::Failing => "●".red(),
+    }
+}
+
+// the characters of a value, the matched ones highlighted
+fn highlight_matched<'a>(value: &str, indices: &[usize]) -> Vec<Span<'a>> {
+    value
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            if indices.contains(&i) {
+                c.to_string().light_yellow()
+            } else {
+                c.to_string().white()
+            }
+        })
+        .collect()
+}
+
+// the rows of the results that fit in their area, less the borders and the header
+fn visible_rows(results_area: Rect) -> usize {
+    results_area.height.saturating_sub(3) as usize
+}
+
+// scroll just far enough to keep the selection on screen
+fn scroll_offset(offset: usize, highlighted: usize, visible: usize, rows: usize) -> usize {
+    let offset = offset.min(rows.saturating_sub(visible));
+    if highlighted < offset {
+        highlighted
+    } else if highlighted >= offset + visible {
+        highlighted + 1 - visible.max(1)
+    } else {
+        offset
+    }
+}
+
+impl SearchPage {
+    // the results, the input and status lines, and the preview pane beside the results if shown
+    fn layout(&self, area: Rect) -> (Rc<[Rect]>, Rect, Option<Rect>) {
+        let chunks = Layout::default()
+            .constraints(
+                [
+                    Constraint::Min(1),
+                    Constraint::Length(1),
+                    Constraint::Length(if self.query_error.is_some() { 1 } else { 0 }),
+                    Constraint::Length(1),
+                ]
+                .as_ref(),
+            )
+            .direction(ratatui::layout::Direction::Vertical)
+            .split(area);
+
+        if !self.columns.preview {
+            let results = chunks[0];
+            return (chunks, results, None);
+        }
+
+        let panes = Layout::default()
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)].as_ref())
+            .direction(Direction::Horizontal)
+            .split(chunks[0]);
+        (chunks, panes[0], Some(panes[1]))
+    }
+
+    // keep the selection on screen and the paging in step with the size of the results
+    fn scroll(&mut self) {
+        let (_, results_area, _) = self.layout(self.area);
+        let visible = visible_rows(results_area);
+        let rows = self.entries(&self.matcher.matches()).len();
+        let highlighted = self.props.highlighted_index.min(rows.saturating_sub(1));
+
+        self.page_size = visible.max(1);
+        self.offset = scroll_offset(self.offset, highlighted, visible, rows);
+    }
+
+    // scroll to the selection, then ask for what the rows on screen and the highlighted
+    // function are missing
+    fn follow_selection(&mut self) {
+        self.scroll();
+        self.request_activity();
+        self.request_preview();
+    }
+
+    // filter the results by the query in the input box, keeping the previous results while it
+    // does not parse
+    fn apply_query(&mut self) {
+        if self.searched.as_deref() == Some(self.input_box.text()) {
+            return;
+        }
+        self.searched = Some(self.input_box.text().to_string());
+
+        match Query::parse(self.input_box.text()) {
+            Ok(mut query) => {
+                query.scope = self.scope;
+                query.pinned = self.favourites.starred.clone();
+                query.pinned.extend(
+                    self.favourites
+                        .recent
+                        .iter()
+                        .filter(|name| !self.favourites.is_starred(name))
+                        .cloned(),
+                );
+                query.only = self.starred_only.then(|| self.favourites.starred.clone());
+                self.matcher.search(query, self.columns.sort);
+                self.query_error = None;
+            }
+            Err(e) => self.query_error = Some(e.to_string()),
+        }
+    }
+
+    fn highlighted(&self) -> Option<Lambda> {
+        let matches = self.matcher.matches();
+        match self.highlighted_entry(&self.entries(&matches)) {
+            Some(Entry::Function(m)) => Some(self.matcher.lambda(m).clone()),
+            _ => None,
+        }
+    }
+
+    // step through the history, older with `older`, leaving it past the most recent query
+    fn browse_history(&mut self, older: bool) {
+        self.history_index = match (self.history_index, older) {
+            (None, true) if !self.history.is_empty() => Some(0),
+            (None, _) => None,
+            (Some(index), true) => Some((index + 1).min(self.history.len() - 1)),
+            (Some(0), false) => None,
+            (Some(index), false) => Some(index - 1),
+        };
+
+        match self.history_index {
+            Some(index) => self.input_box.set_text(&self.history[index]),
+            None => self.input_box.reset(),
+        }
+    }
+
+    fn select(&mut self, index: usize) {
+        let rows = self.entries(&self.matcher.matches()).len();
+        self.props.highlighted_index = index.min(rows.saturating_sub(1));
+    }
+
+    // star the highlighted function, or unstar it, moving it in or out of the pinned ones
+    fn toggle_star(&mut self) {
+        if let Some(lambda) = self.highlighted() {
+            self.favourites.toggle(&lambda.name);
+            self.action_tx
+                .send(Action::ToggleFavourite { lambda })
+                .unwrap();
+            self.searched = None;
+        }
+    }
+
+    // ask for the recent activity of the rows on screen that have not been fetched yet
+    fn request_activity(&mut self) {
+        if !self.show_activity {
+            return;
+        }
+
+        let (_, results_area, _) = self.layout(self.area);
+        let matches = self.matcher.matches();
+        let missing = self
+            .entries(&matches)
+            .iter()
+            .skip(self.offset)
+            .take(visible_rows(results_area))
+            .filter_map(|entry| match entry {
+                Entry::Function(m) => Some(self.matcher.lambda(m)),
+                Entry::Group { .. } => None,
+            })
+            .filter(|lambda| {
+                !self.props.activity.contains_key(&lambda.name)
+                    && !self.requested.contains(&lambda.name)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if missing.is_empty() {
+            return;
+        }
+
+        self.requested
+            .extend(missing.iter().map(|lambda| lambda.name.clone()));
+        self.action_tx
+            .send(Action::FetchRecentActivity { lambdas: missing })
+            .unwrap();
+    }
+
+    // mark the highlighted function, marking a second one compares the two
+    fn mark(&mut self) {
+        let highlighted = match self.highlighted() {
+            Some(lambda) => lambda,
+            None => return,
+        };
+
+        match self.marked.take() {
+            Some(marked) if marked.name == highlighted.name => {}
+            Some(marked) => {
+                self.action_tx
+                    .send(Action::CompareConfiguration {
+                        left: QualifiedLambda {
+                            lambda: marked,
+                            qualifier: None,
+                        },
+                        right: QualifiedLambda {
+                            lambda: highlighted,
+                            qualifier: None,
+                        },
+                        loaded: None,
+                    })
+                    .unwrap();
+            }
+            None => self.marked = Some(highlighted),
+        }
+    }
+
+    // chart the highlighted function against the marked one
+    fn compare_metrics(&mut self) {
+        let highlighted = match self.highlighted() {
+            Some(lambda) => lambda,
+            None => return,
+        };
+
+        match self.marked.take() {
+            Some(marked) if marked.name != highlighted.name => {
+                self.action_tx
+                    .send(Action::CompareMetrics {
+                        left: marked,
+                        right: highlighted,
+                    })
+                    .unwrap();
+            }
+            marked => self.marked = marked,
+        }
+    }
+}
+
+impl Component for SearchPage {
+    fn new(
+        state: &crate::core::State,
+        action_tx: &tokio::sync::mpsc::UnboundedSender<Action>,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        let (props, lambdas, columns, favourites) =
+            if let crate::core::State::Search(search_state) = state {
+                (
+                    SearchProps {
+                        highlighted_index: 0,
+                        activity: search_state.activity.clone(),
+                        preview: search_state.preview.clone(),
+                    },
+                    search_state.lambdas.clone(),
+                    search_state.config.clone(),
+                    search_state.favourites.clone(),
+                )
+            } else {
+                (
+                    SearchProps {
+                        highlighted_index: 0,
+                        activity: HashMap::new(),
+                        preview: None,
+                    },
+                    Arc::new(vec![]),
+                    SearchConfig::default(),
+                    Favourites::default(),
+                )
+            };
+
+        let mut page = Self {
+            action_tx: action_tx.clone(),
+            input_mode: InputMode::Normal,
+            props,
+            matcher: Matcher::new(lambdas, columns.sort),
+            searched: None,
+            scope: SearchScope::default(),
+            favourites,
+            starred_only: false,
+            collapsed: HashSet::new(),
+            columns,
+            column_picker: None,
+            input_box: InputBox::new(state, action_tx),
+            marked: None,
+            show_activity: true,
+            requested: HashSet::new(),
+            query_error: None,
+            history: query::load_history(),
+            history_index: None,
+            area: Rect::default(),
+            offset: 0,
+            page_size: 1,
+            pending_g: false,
+            preview_candidate: None,
+            preview_requested: None,
+            preview_failed: None,
+        };
+        page.apply_query();
+        page
+    }
+
+    fn name(&self) -> &str {
+        "Search"
+    }
+
+    fn move_with_state(self, state: &crate::core::State) -> Self
+    where
+        Self: Sized,
+    {
+        // fetched activity refreshes the results already on screen, keep typing where it was
+        let refreshed =
+            matches!(state, crate::core::State::Search(_)) && !self.matcher.lambdas().is_empty();
+
+        let (props, lambdas, columns, favourites) =
+            if let crate::core::State::Search(search_state) = state {
+                (
+                    SearchProps {
+                        highlighted_index: self.props.highlighted_index,
+                        activity: search_state.activity.clone(),
+                        preview: search_state.preview.clone(),
+                    },
+                    search_state.lambdas.clone(),
+                    search_state.config.clone(),
+                    search_state.favourites.clone(),
+                )
+            } else {
+                (
+                    SearchProps {
+                        highlighted_index: 0,
+                        activity: HashMap::new(),
+                        preview: None,
+                    },
+                    Arc::new(vec![]),
+                    self.columns.clone(),
+                    self.favourites.clone(),
+                )
+            };
+        // like the columns, the page stars functions itself and a refresh may carry the old ones
+        let favourites = if refreshed {
+            self.favourites
+        } else {
+            favourites
+        };
+
+        // the list is shared with the state, so an unchanged list keeps its matcher and results
+        let unchanged = Arc::ptr_eq(self.matcher.lambdas(), &lambdas)
+            || (lambdas.is_empty() && self.matcher.lambdas().is_empty());
+        let (matcher, searched) = if unchanged {
+            // a dashboard opened since changes the recently viewed
+            let searched = self.searched.filter(|_| refreshed);
+            (self.matcher, searched)
+        } else {
+            (Matcher::new(lambdas, columns.sort), None)
+        };
+
+        // what was asked for is asked again once the page is back
+        let (requested, preview_requested, preview_failed) = if refreshed {
+            (self.requested, self.preview_requested, self.preview_failed)
+        } else {
+            (HashSet::new(), None, None)
+        };
+
+        // a failed preview is no longer waited for
+        let failed = props
+            .preview
+            .as_ref()
+            .filter(|preview| preview.error.is_some())
+            .map(|preview| preview.lambda.name.clone());
+        let (preview_requested, preview_failed) = match failed {
+            Some(failed) if preview_requested.as_ref() == Some(&failed) => (None, Some(failed)),
+            _ => (preview_requested, preview_failed),
+        };
+
+        let mut page = Self {
+            action_tx: self.action_tx,
+            input_mode: if refreshed {
+                self.input_mode
+            } else {
+                InputMode::Normal
+            },
+            props,
+            matcher,
+            searched,
+            scope: self.scope,
+            favourites,
+            starred_only: self.starred_only,
+            collapsed: self.collapsed,
+            // the page changes its columns itself, a refresh may still carry the old ones
+            columns: if refreshed { self.columns } else { columns },
+            column_picker: self.column_picker.filter(|_| refreshed),
+            input_box: self.input_box,
+            marked: self.marked,
+            show_activity: self.show_activity,
+            requested,
+            query_error: self.query_error,
+            history: self.history,
+            history_index: self.history_index,
+            area: self.area,
+            offset: self.offset,
+            page_size: self.page_size,
+            pending_g: false,
+            preview_candidate: self.preview_candidate,
+            preview_requested,
+            preview_failed,
+        };
+        page.apply_query();
+        page.scroll();
+        page
+    }
+
+    fn handle_key_event(&mut self, key: crossterm::event::KeyEvent) {
+        if let Some(cursor) = self.column_picker {
+            self.handle_column_picker_key(key, cursor);
+            return;
+        }
+
+        if key.code == crossterm::event::KeyCode::Esc {
+            self.input_mode = InputMode::Normal;
+            return;
+        }
+
+        let contains_control = key
+            .modifiers
+            .contains(crossterm::event::KeyModifiers::CONTROL);
+
+        if key.code == crossterm::event::KeyCode::Enter {
+            if self.toggle_group() {
+                return;
+            }
+            if let Some(lambda) = self.highlighted() {
+                query::save_history(&mut self.history, self.input_box.text());
+                self.history_index = None;
+                self.action_tx
+                    .send(Action::PerformSearch { lambda })
+                    .unwrap();
+            }
+            return;
+        }
+
+        let page_size = self.page_size;
+        match key.code {
+            crossterm::event::KeyCode::Tab => {
+                self.scope = self.scope.next();
+                self.searched = None;
+            }
+            crossterm::event::KeyCode::Char('n') if contains_control => {
+                self.select(self.props.highlighted_index + 1);
+            }
+            crossterm::event::KeyCode::Char('p') if contains_control => {
+                self.select(self.props.highlighted_index.saturating_sub(1));
+            }
+            crossterm::event::KeyCode::PageDown => {
+                self.select(self.props.highlighted_index + page_size);
+            }
+            crossterm::event::KeyCode::PageUp => {
+                self.select(self.props.highlighted_index.saturating_sub(page_size));
+            }
+            _ => {}
+        }
+
+        let pending_g = std::mem::take(&mut self.pending_g);
+
+        if self.input_mode == InputMode::Normal {
+            match key.code {
+                crossterm::event::KeyCode::Char('j') | crossterm::event::KeyCode::Down => {
+                    self.select(self.props.highlighted_index + 1);
+                }
+                crossterm::event::KeyCode::Char('k') | crossterm::event::KeyCode::Up => {
+                    self.select(self.props.highlighted_index.saturating_sub(1));
+                }
+                crossterm::event::KeyCode::Home => self.select(0),
+                crossterm::event::KeyCode::Char('g') if pending_g => self.select(0),
+                crossterm::event::KeyCode::Char('g') => self.pending_g = true,
+                crossterm::event::KeyCode::End | crossterm::event::KeyCode::Char('G') => {
+                    self.select(usize::MAX);
+                }
+                crossterm::event::KeyCode::Char('i') => {
+                    self.input_mode = InputMode::Insert;
+                }
+                crossterm::event::KeyCode::Char('q') => {
+                    self.action_tx.send(Action::Quit).unwrap();
+                }
+                crossterm::event::KeyCode::Char('m') => self.mark(),
+                crossterm::event::KeyCode::Char('c') => self.compare_metrics(),
+                crossterm::event::KeyCode::Char('r') => {
+                    self.action_tx.send(Action::DriftReport).unwrap();
+                }
+                crossterm::event::KeyCode::Char('h') => {
+                    self.show_activity = !self.show_activity;
+                }
+                crossterm::event::KeyCode::Char('o') => self.column_picker = Some(0),
+                crossterm::event::KeyCode::Char('*') => self.toggle_star(),
+                crossterm::event::KeyCode::Char('t') => self.cycle_group_by(),
+                crossterm::event::KeyCode::Char(' ') => {
+                    self.toggle_group();
+                }
+                crossterm::event::KeyCode::Char('F') => {
+                    self.starred_only = !self.starred_only;
+                    self.searched = None;
+                }
+                crossterm::event::KeyCode::Char('p') => {
+                    self.columns.preview = !self.columns.preview;
+                    self.save_columns();
+                }
+                crossterm::event::KeyCode::Char('s') => self.cycle_sort(),
+                crossterm::event::KeyCode::Char('S') => self.reverse_sort(),
+                crossterm::event::KeyCode::Char('f') => {
+                    self.action_tx
+                        .send(Action::Fleet {
+                            window: FleetWindow::Day,
+                        })
+                        .unwrap();
+                }
+                _ => {}
+            }
+        } else {
+            match key.code {
+                crossterm::event::KeyCode::Up => self.browse_history(true),
+                crossterm::event::KeyCode::Down => self.browse_history(false),
+                _ => self.input_box.handle_key_event(key),
+            }
+        }
+
+        self.apply_query();
+        self.select(self.props.highlighted_index);
+        self.follow_selection();
+    }
+
+    fn handle_tick(&mut self, area: Rect) {
+        self.area = area;
+        self.follow_selection();
+    }
+}
+
+impl ComponentRender<()> for SearchPage {
+    fn render(&self, frame: &mut Frame, _: ()) {
+        let (chunks, results_area, preview_area) = self.layout(frame.size());
+
+        if let Some(error) = &self.query_error {
+            frame.render_widget(
+                Text::styled(error.as_str(), Style::default().red()),
+                chunks[2],
+            );
+        }
+
+        let help_text = if self.input_mode == InputMode::Insert {
+            Text::styled(
+                "help: [esc] normal mode, [ctrl+n] next, [ctrl+p] previous, [up/down] history, [tab] search scope, filters: runtime: mem: timeout: arch: tag:key=value layer: trigger:",
+                Style::default().fg(Color::White).bg(Color::DarkGray),
+            )
+        } else {
+            Text::styled(
+                "help: [q] quit, [i] insert mode, [enter] perform search, [j/k] next/previous, [gg/G] first/last, [pgup/pgdn] page, [m] mark/compare config, [c] compare metrics with marked, [r] drift report, [f] fleet overview, [h] toggle activity, [o] columns, [s/S] sort, [tab] search scope, [p] preview, [*] star, [F] starred only, [t] group, [space] fold group",
+                Style::default().fg(Color::White).bg(Color::DarkGray),
+            )
+        };
+
+        frame.render_widget(help_text, chunks[3]);
+
+        let matches = self.matcher.matches();
+        let entries = self.entries(&matches);
+        let highlighted_index = self
+            .props
+            .highlighted_index
+            .min(entries.len().saturating_sub(1));
+
+        if let Some(preview_area) = preview_area {
+            let highlighted = match entries.get(highlighted_index) {
+                Some(Entry::Function(m)) => Some(self.matcher.lambda(m)),
+                _ => None,
+            };
+            self.render_preview(frame, preview_area, highlighted);
+        }
+
+        // the offset is kept by the key and tick handlers, this only catches up with a resize
+        // drawn before the next tick
+        let visible = visible_rows(results_area);
+        let offset = scroll_offset(self.offset, highlighted_index, visible, entries.len());
+
+        // only the rows on screen are built, the list can run into thousands
+        let rows: Vec<Row> = entries
+            .iter()
+            .skip(offset)
+            .take(visible)
+            .map(|entry| {
+                let m = match entry {
+                    Entry::Function(m) => m,
+                    Entry::Group {
+                        name,
+                        functions,
+                        with_errors,
+                        collapsed,
+                    } => {
+                        let mut heading = vec![
+                            if *collapsed { "▸ " } else { "▾ " }.white(),
+                            name.clone().bold(),
+                            format!(" {} functions", functions).dark_gray(),
+                        ];
+                        if *with_errors > 0 {
+                            heading.push(format!(", {} with errors", with_errors).red());
+                        }
+                        return Row::new(vec![Line::from(heading)]);
+                    }
+                };
+
+                let lambda = self.matcher.lambda(m);
+                let mut colorised: Vec<Span<'_>> = Vec::new();
+
+                // functions are set in under the heading of their group
+                if self.columns.group_by.is_some() {
+                    colorised.push("  ".white());
+                }
+
+                let is_marked = self
+                    .marked
+                    .as_ref()
+                    .map(|marked| marked.name == lambda.name)
+                    .unwrap_or(false);
+
+                if is_marked {
+                    colorised.push("* ".light_yellow());
+                } else {
+                    colorised.push("  ".white());
+                }
+
+                if self.favourites.is_starred(&lambda.name) {
+                    colorised.push("★ ".yellow());
+                } else if self.favourites.recent.contains(&lambda.name) {
+                    colorised.push("◷ ".dark_gray());
+                }
+
+                // the matched characters are only in the name when the name matched
+                let name_indices: &[usize] = if m.field == Field::Name {
+                    &m.indices
+                } else {
+                    &[]
+                };
+                colorised.extend(highlight_matched(&lambda.name, name_indices));
+
+                let mut cells = vec![Line::from(colorised)];
+                if self.scope != SearchScope::Name {
+                    let mut matched = vec![format!("{}: ", m.field.label()).dark_gray()];
+                    matched.extend(highlight_matched(&m.value, &m.indices));
+                    cells.push(Line::from(matched));
+                }
+                cells.extend(
+                    self.columns
+                        .columns
+                        .iter()
+                        .map(|column| column.value(lambda).into()),
+                );
+
+                if self.show_activity {
+                    match self.props.activity.get(&lambda.name) {
+                        Some(activity) => cells.extend([
+                            sparkline(&activity.invocations).green().into(),
+                            format!("{:.0}", activity.errors).into(),
+                            health_badge(activity.health()).into(),
+                        ]),
+                        None => cells.extend(["".into(), "".into(), "".into()]),
+                    }
+                }
+
+                Row::new(cells)
+            })
+            .collect::<Vec<_>>();
+
+        let (widths, header) = self.header();
+
+        let position = if entries.is_empty() {
+            0
+        } else {
+            highlighted_index + 1
+        };
+
+        let starred = if self.starred_only { "starred " } else { "" };
+        let grouped = match self.columns.group_by {
+            Some(group_by) => format!(", by {}", group_by.title(self.columns.group_tag.as_deref())),
+            None => String::new(),
+        };
+
+        let table = Table::new(rows, widths)
+            .column_spacing(1)
+            .header(Row::new(header).underlined().bold())
+            .block(
+                Block::default()
+                    .title(if self.matcher.pending() {
+                        format!(
+                            "Results ({} of {}, searching {}{}{}...)",
+                            position,
+                            entries.len(),
+                            starred,
+                            self.scope.label(),
+                            grouped
+                        )
+                    } else {
+                        format!(
+                            "Results ({} of {}, in {}{}{})",
+                            position,
+                            entries.len(),
+                            starred,
+                            self.scope.label(),
+                            grouped
+                        )
+                    })
+                    .borders(Borders::ALL),
+            )
+            .highlight_style(Style::default().bg(Color::DarkGray))
+            .highlight_symbol(">> ");
+
+        let mut table_state = TableState::default()
+            .with_selected((!entries.is_empty()).then_some(highlighted_index - offset));
+        frame.render_stateful_widget(table, results_area, &mut table_state);
+
+        let mut scrollbar_state = ScrollbarState::new(entries.len()).position(highlighted_index);
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight),
+            results_area.inner(&Margin {
+                vertical: 1,
+                horizontal: 0,
+            }),
+            &mut scrollbar_state,
+        );
+
+        let mode_text = if self.input_mode == InputMode::Insert {
+            Text::styled(
+                " INSERT ",
+                Style::default().fg(Color::Black).bg(Color::Yellow),
+            )
+        } else {
+            Text::styled(
+                " NORMAL ",
+                Style::default().fg(Color::Black).bg(Color::LightBlue),
+            )
+        };
+
+        let input_chunks = Layout::default()
+            .constraints(
+                [
+                    Constraint::Length(8),
+                    Constraint::Length(8),
+                    Constraint::Min(1),
+                ]
+                .as_ref(),
+            )
+            .direction(ratatui::layout::Direction::Horizontal)
+            .split(chunks[1]);
+
+        frame.render_widget(mode_text, input_chunks[0]);
+
+        frame.render_widget(
+            Text::styled(
+                " search: ",
+                Style::default().fg(Color::White).bg(Color::Black),
+            ),
+            input_chunks[1],
+        );
+
+        let input_box_props = crate::app::component::RenderProps {
+            title: "Search".to_string(),
+            area: input_chunks[2],
+            border_color: Color::White,
+            show_cursor: self.input_mode == InputMode::Insert,
+        };
+
+        self.input_box.render(frame, input_box_props);
+
+        if let Some(cursor) = self.column_picker {
+            self.render_column_picker(frame, cursor);
+        }
+    }
+}
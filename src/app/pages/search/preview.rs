@@ -0,0 +1,173 @@
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Stylize},
+    text::Line,
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::{
+    app::chart::{render_series_chart, Series},
+    config::Column,
+    core::{Action, EventSourceMappingState, Lambda, Preview},
+};
+
+use super::SearchPage;
+
+/// How long the selection has to rest on a function before its preview is fetched, so scrolling
+/// through the results does not fetch every function passed on the way.
+const PREVIEW_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// The configuration shown in the preview, in order.
+const PREVIEW_COLUMNS: [Column; 7] = [
+    Column::Runtime,
+    Column::Memory,
+    Column::Timeout,
+    Column::Architecture,
+    Column::LastModified,
+    Column::CodeSize,
+    Column::Description,
+];
+
+impl SearchPage {
+    // ask for the preview of the highlighted function once the selection has rested on it
+    pub(super) fn request_preview(&mut self) {
+        if !self.columns.preview {
+            return;
+        }
+        let highlighted = match self.highlighted() {
+            Some(lambda) => lambda,
+            None => return,
+        };
+
+        match &self.preview_candidate {
+            Some((name, since)) if *name == highlighted.name => {
+                if since.elapsed() < PREVIEW_DEBOUNCE {
+                    return;
+                }
+            }
+            _ => {
+                self.preview_candidate = Some((highlighted.name.clone(), Instant::now()));
+                self.preview_failed = None;
+                return;
+            }
+        }
+
+        if self.preview_requested.as_deref() == Some(highlighted.name.as_str())
+            || self.preview_failed.as_deref() == Some(highlighted.name.as_str())
+        {
+            return;
+        }
+        self.preview_requested = Some(highlighted.name.clone());
+
+        self.action_tx
+            .send(Action::Preview {
+                lambda: highlighted,
+            })
+            .unwrap();
+    }
+
+    pub(super) fn render_preview(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        highlighted: Option<&Lambda>,
+    ) {
+        let lambda = match highlighted {
+            Some(lambda) => lambda,
+            None => {
+                frame.render_widget(
+                    Block::default().title("Preview").borders(Borders::ALL),
+                    area,
+                );
+                return;
+            }
+        };
+
+        let block = Block::default()
+            .title(format!("Preview: {}", lambda.name))
+            .borders(Borders::ALL);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let preview = self
+            .props
+            .preview
+            .as_ref()
+            .filter(|preview| preview.lambda.name == lambda.name);
+
+        let mut lines = PREVIEW_COLUMNS
+            .iter()
+            .map(|column| {
+                Line::from(vec![
+                    format!("{}: ", column.title()).dark_gray(),
+                    column.value(lambda).white(),
+                ])
+            })
+            .collect::<Vec<_>>();
+        if !lambda.layers.is_empty() {
+            lines.push(Line::from(vec![
+                "layers: ".dark_gray(),
+                lambda
+                    .layers
+                    .iter()
+                    .map(|arn| crate::report::layer_name(arn))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+                    .white(),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from("triggers".bold()));
+        match preview {
+            Some(Preview {
+                error: Some(error), ..
+            }) => {
+                lines.push(Line::from(format!("failed to load: {}", error).red()));
+            }
+            Some(preview) if preview.event_source_mappings.is_empty() => {
+                lines.push(Line::from("none".dark_gray()));
+            }
+            Some(preview) => {
+                lines.extend(preview.event_source_mappings.iter().map(|event_source| {
+                    Line::from(vec![
+                        match event_source.state() {
+                            EventSourceMappingState::Enabled => "● ".green(),
+                            EventSourceMappingState::Disabled => "● ".red(),
+                        },
+                        format!("{} ", event_source.type_name()).white(),
+                        event_source.name().dark_gray(),
+                    ])
+                }));
+            }
+            None => lines.push(Line::from("loading...".dark_gray())),
+        }
+
+        let chunks = Layout::default()
+            .constraints([Constraint::Min(1), Constraint::Length(12)].as_ref())
+            .direction(Direction::Vertical)
+            .split(inner);
+
+        frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+        if let Some(preview) = preview.filter(|preview| preview.error.is_none()) {
+            let series = preview
+                .metrics
+                .iter()
+                .map(|metric| Series {
+                    name: metric.name.clone(),
+                    metric,
+                    color: if metric.name == "errors" {
+                        Color::Red
+                    } else {
+                        Color::Green
+                    },
+                })
+                .collect::<Vec<_>>();
+            render_series_chart(frame, chunks[1], "last hour", &series, 1.0);
+        }
+    }
+}
@@ -0,0 +1,586 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style, Stylize},
+    text::Text,
+    widgets::{Block, Borders, Gauge},
+    Frame,
+};
+
+use crate::{
+    app::{
+        chart::render_charts,
+        component::{Component, ComponentRender, ConfirmDialog},
+    },
+    core::{Action, Alias, Lambda, State, Version, VersionMetrics},
+};
+
+pub struct VersionProps {
+    lambda: Option<Lambda>,
+    alias: Option<Alias>,
+    version: Option<Version>,
+    versions: Vec<Version>,
+    version_metrics: Vec<VersionMetrics>,
+    status: Option<String>,
+}
+
+/// The share of traffic the target version receives at each step of a canary deployment.
+const CANARY_STEPS: [f64; 3] = [0.1, 0.5, 1.0];
+
+pub struct VersionPage {
+    action_tx: tokio::sync::mpsc::UnboundedSender<Action>,
+    props: VersionProps,
+    /// the version traffic is being shifted to
+    target: Option<String>,
+    /// the routing to apply once confirmed
+    pending: Option<Alias>,
+}
+
+fn props_from_state(state: &State) -> VersionProps {
+    if let State::Version(version) = state {
+        VersionProps {
+            lambda: Some(version.lambda.clone()),
+            alias: version.alias.clone(),
+            version: version.version.clone(),
+            versions: version.versions.clone(),
+            version_metrics: version.version_metrics.clone(),
+            status: version.status.clone(),
+        }
+    } else {
+        VersionProps {
+            lambda: None,
+            alias: None,
+            version: None,
+            versions: vec![],
+            version_metrics: vec![],
+            status: None,
+        }
+    }
+}
+
+fn describe_routing(alias: &Alias) -> String {
+    alias
+        .traffic_split()
+        .iter()
+        .map(|(version, weight)| format!("{} ({:.0}%)", version, weight * 100.0))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl VersionPage {
+    // published versions other than the alias' primary version that traffic can be shifted to
+    fn target_candidates(&self) -> Vec<String> {
+        let primary = self
+            .props
+            .alias
+            .as_ref()
+            .map(|a| a.function_version.clone())
+            .unwrap_or_default();
+
+        self.props
+            .versions
+            .iter()
+            .map(|v| v.version.clone())
+            .filter(|v| v != "$LATEST" && *v != primary)
+            .collect()
+    }
+
+    // default to the version already receiving traffic, or else the newest version
+    fn default_target(&self) -> Option<String> {
+        let alias = self.props.alias.as_ref()?;
+        alias
+            .additional_version_weights
+            .first()
+            .map(|(version, _)| version.clone())
+            .or_else(|| self.target_candidates().first().cloned())
+    }
+
+    fn cycle_target(&mut self) {
+        let candidates = self.target_candidates();
+        if candidates.is_empty() {
+            return;
+        }
+
+        let next = match &self.target {
+            Some(target) => candidates
+                .iter()
+                .position(|c| c == target)
+                .map(|i| (i + 1) % candidates.len())
+                .unwrap_or(0),
+            None => 0,
+        };
+
+        self.target = Some(candidates[next].clone());
+    }
+
+    fn target_weight(alias: &Alias, target: &str) -> f64 {
+        if alias.function_version == target {
+            return 1.0;
+        }
+
+        alias
+            .additional_version_weights
+            .iter()
+            .find(|(version, _)| version == target)
+            .map(|(_, weight)| *weight)
+            .unwrap_or(0.0)
+    }
+
+    // shifting all the traffic to the target promotes it to the primary version, the version
+    // being shifted away from is recorded when the shift starts so that it can be rolled back to
+    fn with_target_weight(alias: &Alias, target: &str, weight: f64) -> Alias {
+        let alias = if alias.additional_version_weights.is_empty()
+            && alias.function_version != target
+            && weight > 0.0
+        {
+            alias.with_previous_version(Some(&alias.function_version))
+        } else {
+            alias.clone()
+        };
+
+        if weight >= 1.0 {
+            Alias {
+                function_version: target.to_string(),
+                additional_version_weights: vec![],
+                ..alias
+            }
+        } else if weight <= 0.0 {
+            // back to where it started, there is nothing left to roll back
+            Alias {
+                additional_version_weights: vec![],
+                ..alias.with_previous_version(None)
+            }
+        } else {
+            Alias {
+                additional_version_weights: vec![(target.to_string(), weight)],
+                ..alias
+            }
+        }
+    }
+
+    fn step(&mut self, forward: bool) {
+        let (alias, target) = match (&self.props.alias, &self.target) {
+            (Some(alias), Some(target)) => (alias, target),
+            _ => return,
+        };
+
+        let current = Self::target_weight(alias, target);
+
+        // once promoted, rolling back to the recorded previous version is the way out
+        if current >= 1.0 {
+            return;
+        }
+
+        let weight = if forward {
+            CANARY_STEPS
+                .iter()
+                .find(|step| **step > current + f64::EPSILON)
+                .copied()
+        } else {
+            Some(
+                CANARY_STEPS
+                    .iter()
+                    .rev()
+                    .find(|step| **step < current - f64::EPSILON)
+                    .copied()
+                    .unwrap_or(0.0),
+            )
+        };
+
+        if let Some(weight) = weight {
+            self.pending = Some(Self::with_target_weight(alias, target, weight));
+        }
+    }
+
+    // drop any canary routing, or else point the alias back at the version recorded when the
+    // traffic started shifting
+    fn rollback(&mut self) {
+        let alias = match &self.props.alias {
+            Some(alias) => alias,
+            None => return,
+        };
+
+        if !alias.additional_version_weights.is_empty() {
+            self.pending = Some(Alias {
+                additional_version_weights: vec![],
+                ..alias.with_previous_version(None)
+            });
+            return;
+        }
+
+        match alias.previous_version() {
+            Some(previous) if previous != alias.function_version => {
+                self.pending = Some(Alias {
+                    function_version: previous.to_string(),
+                    additional_version_weights: vec![],
+                    ..alias.with_previous_version(None)
+                });
+            }
+            _ => {
+                self.props.status = Some(format!(
+                    "can't roll back {}, the version it pointed at before {} is unknown",
+                    alias.name, alias.function_version
+                ));
+            }
+        }
+    }
+
+    fn confirm(&mut self) {
+        if let (Some(lambda), Some(alias)) = (&self.props.lambda, self.pending.take()) {
+            self.action_tx
+                .send(Action::UpdateAlias {
+                    lambda: lambda.clone(),
+                    alias,
+                    versions: self.props.versions.clone(),
+                })
+                .unwrap();
+        }
+    }
+
+    fn confirm_dialog(&self) -> Option<ConfirmDialog> {
+        let current = self.props.alias.as_ref()?;
+        let pending = self.pending.as_ref()?;
+
+        Some(ConfirmDialog {
+            title: format!("Update {}", current.name),
+            lines: vec![
+                format!("from: {}", describe_routing(current)),
+                format!("to:   {}", describe_routing(pending)),
+            ],
+        })
+    }
+}
+
+impl Component for VersionPage {
+    fn new(state: &State, action_tx: &tokio::sync::mpsc::UnboundedSender<Action>) -> Self
+    where
+        Self: Sized,
+    {
+        let mut page = Self {
+            action_tx: action_tx.clone(),
+            props: props_from_state(state),
+            target: None,
+            pending: None,
+        };
+        page.target = page.default_target();
+        page
+    }
+
+    fn name(&self) -> &str {
+        "Version"
+    }
+
+    fn move_with_state(self, state: &State) -> Self
+    where
+        Self: Sized,
+    {
+        let mut page = Self {
+            action_tx: self.action_tx,
+            props: props_from_state(state),
+            target: self.target,
+            pending: None,
+        };
+        // keep the target while shifting traffic, unless it is no longer a candidate
+        if !page
+            .target
+            .as_ref()
+            .map(|t| page.target_candidates().contains(t))
+            .unwrap_or(false)
+        {
+            page.target = page.default_target();
+        }
+        page
+    }
+
+    fn handle_key_event(&mut self, key: crossterm::event::KeyEvent) {
+        if self.pending.is_some() {
+            match key.code {
+                crossterm::event::KeyCode::Char('y') => self.confirm(),
+                crossterm::event::KeyCode::Char('n') | crossterm::event::KeyCode::Esc => {
+                    self.pending = None;
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        if self.props.alias.is_some() {
+            match key.code {
+                crossterm::event::KeyCode::Char('t') => self.cycle_target(),
+                crossterm::event::KeyCode::Char(']') => self.step(true),
+                crossterm::event::KeyCode::Char('[') => self.step(false),
+                crossterm::event::KeyCode::Char('b') => self.rollback(),
+                _ => {}
+            }
+        }
+
+        match key.code {
+            crossterm::event::KeyCode::Char('q') => {
+                self.action_tx.send(Action::Quit).unwrap();
+            }
+            crossterm::event::KeyCode::Esc => {
+                if let Some(lambda) = &self.props.lambda {
+                    self.action_tx
+                        .send(Action::PerformSearch {
+                            lambda: lambda.clone(),
+                        })
+                        .unwrap();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl ComponentRender<()> for VersionPage {
+    fn render(&self, frame: &mut Frame, _: ()) {
+        let split_height = if self.props.alias.is_some() {
+            self.props.version_metrics.len() as u16 + 2
+        } else {
+            0
+        };
+
+        let chunks = Layout::default()
+            .constraints(
+                [
+                    Constraint::Length(1),
+                    Constraint::Length(split_height),
+                    Constraint::Min(1),
+                    Constraint::Length(if self.props.status.is_some() { 1 } else { 0 }),
+                    Constraint::Length(1),
+                ]
+                .as_ref(),
+            )
+            .direction(Direction::Vertical)
+            .split(frame.size());
+
+        let qualifier = match (&self.props.alias, &self.props.version) {
+            (Some(alias), _) => format!("{} -> {}", alias.name, alias.function_version),
+            (None, Some(version)) => version.version.clone(),
+            (None, None) => "".to_string(),
+        };
+
+        let title = match &self.props.lambda {
+            Some(lambda) => format!("{}:{}", lambda.name, qualifier),
+            None => qualifier,
+        };
+
+        frame.render_widget(
+            Text::styled(
+                title,
+                Style::default().fg(Color::White).bold().bg(Color::DarkGray),
+            ),
+            chunks[0],
+        );
+
+        // show how the alias currently splits traffic between its versions
+        if self.props.alias.is_some() {
+            let block = Block::default()
+                .title(match &self.target {
+                    Some(target) => format!("Traffic (target version {})", target),
+                    None => "Traffic".to_string(),
+                })
+                .borders(Borders::ALL);
+            let inner = block.inner(chunks[1]);
+            frame.render_widget(block, chunks[1]);
+
+            let gauge_chunks = Layout::default()
+                .constraints(
+                    self.props
+                        .version_metrics
+                        .iter()
+                        .map(|_| Constraint::Length(1))
+                        .collect::<Vec<_>>(),
+                )
+                .direction(Direction::Vertical)
+                .split(inner);
+
+            self.props
+                .version_metrics
+                .iter()
+                .zip(gauge_chunks.iter())
+                .for_each(|(version, chunk)| {
+                    let weight = version.weight.unwrap_or(1.0).clamp(0.0, 1.0);
+                    let gauge = Gauge::default()
+                        .gauge_style(Style::default().fg(Color::Green).bg(Color::DarkGray))
+                        .ratio(weight)
+                        .label(format!(
+                            "version {}: {:.0}%",
+                            version.version,
+                            weight * 100.0
+                        ));
+                    frame.render_widget(gauge, *chunk);
+                });
+        }
+
+        let version_chunks = Layout::default()
+            .constraints(
+                self.props
+                    .version_metrics
+                    .iter()
+                    .map(|_| Constraint::Ratio(1, self.props.version_metrics.len() as u32))
+                    .collect::<Vec<_>>(),
+            )
+            .direction(Direction::Horizontal)
+            .split(chunks[2]);
+
+        self.props
+            .version_metrics
+            .iter()
+            .zip(version_chunks.iter())
+            .for_each(|(version, chunk)| {
+                let block = Block::default()
+                    .title(format!("Version {}", version.version))
+                    .borders(Borders::ALL);
+                let inner = block.inner(*chunk);
+                frame.render_widget(block, *chunk);
+                render_charts(frame, inner, &version.metrics, None);
+            });
+
+        if let Some(status) = &self.props.status {
+            frame.render_widget(
+                Text::styled(status.as_str(), Style::default().yellow()),
+                chunks[3],
+            );
+        }
+
+        let help_text = if self.props.alias.is_some() {
+            Text::styled(
+                "help: [q] quit, [esc] dashboard, [t] next target version, []] shift traffic forward, [[] shift traffic back, [b] roll back",
+                Style::default().fg(Color::White).bg(Color::DarkGray),
+            )
+        } else {
+            Text::styled(
+                "help: [q] quit, [esc] dashboard",
+                Style::default().fg(Color::White).bg(Color::DarkGray),
+            )
+        };
+
+        frame.render_widget(help_text, chunks[4]);
+
+        if let Some(dialog) = self.confirm_dialog() {
+            dialog.render(frame, ());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alias(function_version: &str, weights: &[(&str, f64)]) -> Alias {
+        Alias {
+            name: "live".to_string(),
+            function_version: function_version.to_string(),
+            description: "production traffic".to_string(),
+            additional_version_weights: weights
+                .iter()
+                .map(|(version, weight)| (version.to_string(), *weight))
+                .collect(),
+            previous_version: None,
+        }
+    }
+
+    fn page(alias: Alias) -> VersionPage {
+        let (action_tx, _) = tokio::sync::mpsc::unbounded_channel();
+        VersionPage {
+            action_tx,
+            props: VersionProps {
+                lambda: None,
+                alias: Some(alias),
+                version: None,
+                versions: vec![],
+                version_metrics: vec![],
+                status: None,
+            },
+            target: Some("2".to_string()),
+            pending: None,
+        }
+    }
+
+    fn stepped(alias: Alias, forward: bool) -> Option<Alias> {
+        let mut page = page(alias);
+        page.step(forward);
+        page.pending
+    }
+
+    #[test]
+    fn step_forward_starts_the_canary_and_records_the_previous_version() {
+        let pending = stepped(alias("1", &[]), true).unwrap();
+
+        assert_eq!(pending.function_version, "1");
+        assert_eq!(
+            pending.additional_version_weights,
+            vec![("2".to_string(), 0.1)]
+        );
+        assert_eq!(pending.previous_version(), Some("1"));
+        assert_eq!(pending.description, "production traffic");
+    }
+
+    #[test]
+    fn step_forward_moves_through_the_canary_steps() {
+        let pending = stepped(alias("1", &[("2", 0.1)]), true).unwrap();
+        assert_eq!(
+            pending.additional_version_weights,
+            vec![("2".to_string(), 0.5)]
+        );
+
+        let promoted = stepped(alias("1", &[("2", 0.5)]), true).unwrap();
+        assert_eq!(promoted.function_version, "2");
+        assert!(promoted.additional_version_weights.is_empty());
+    }
+
+    #[test]
+    fn step_back_moves_through_the_canary_steps() {
+        let pending = stepped(alias("1", &[("2", 0.5)]), false).unwrap();
+        assert_eq!(
+            pending.additional_version_weights,
+            vec![("2".to_string(), 0.1)]
+        );
+
+        let cleared = stepped(
+            alias("1", &[("2", 0.1)]).with_previous_version(Some("1")),
+            false,
+        )
+        .unwrap();
+        assert_eq!(cleared.function_version, "1");
+        assert!(cleared.additional_version_weights.is_empty());
+        assert_eq!(cleared.previous_version(), None);
+    }
+
+    #[test]
+    fn step_does_nothing_once_promoted() {
+        assert!(stepped(alias("2", &[]), true).is_none());
+        assert!(stepped(alias("2", &[]), false).is_none());
+    }
+
+    #[test]
+    fn rollback_drops_the_canary_routing() {
+        let mut page = page(alias("1", &[("2", 0.5)]).with_previous_version(Some("1")));
+        page.rollback();
+
+        let pending = page.pending.unwrap();
+        assert_eq!(pending.function_version, "1");
+        assert!(pending.additional_version_weights.is_empty());
+        assert_eq!(pending.previous_version(), None);
+        assert_eq!(pending.description, "production traffic");
+    }
+
+    #[test]
+    fn rollback_points_a_promoted_alias_at_the_previous_version() {
+        let mut page = page(alias("2", &[]).with_previous_version(Some("1")));
+        page.rollback();
+
+        let pending = page.pending.unwrap();
+        assert_eq!(pending.function_version, "1");
+        assert_eq!(pending.previous_version(), None);
+    }
+
+    #[test]
+    fn rollback_refuses_without_a_previous_version() {
+        let mut page = page(alias("2", &[]));
+        page.rollback();
+
+        assert!(page.pending.is_none());
+        assert!(page.props.status.is_some());
+    }
+}
@@ -6,11 +6,15 @@ use aws_sdk_cloudwatch::types::{
     Dimension, MetricDataQuery,
 };
 
-use crate::core::Metric;
+use crate::{
+    config::{CustomMetric, MetricsConfig},
+    core::{FleetMetrics, Lambda, Metric, RecentActivity},
+};
 
-pub fn build_metric(
+pub fn build_query(
     metric_id: &str,
-    lambda_name: &str,
+    namespace: &str,
+    dimensions: Vec<Dimension>,
     metric_name: &str,
     stat: &str,
     period: i32,
@@ -22,14 +26,9 @@ pub fn build_metric(
                 .stat(stat)
                 .metric(
                     MetricBuilder::default()
-                        .namespace("AWS/Lambda")
+                        .namespace(namespace)
                         .metric_name(metric_name)
-                        .dimensions(
-                            Dimension::builder()
-                                .name("FunctionName")
-                                .value(lambda_name)
-                                .build(),
-                        )
+                        .set_dimensions(Some(dimensions))
                         .build(),
                 )
                 .period(period)
@@ -38,33 +37,95 @@ pub fn build_metric(
         .build()
 }
 
-// get invocations of a lambda for the past 24 hours
-pub async fn metrics(client: &aws_sdk_cloudwatch::Client, arn: &String) -> Result<Vec<Metric>> {
-    let period = 60;
+pub fn build_expression(
+    metric_id: &str,
+    expression: &str,
+    label: &str,
+    period: i32,
+) -> MetricDataQuery {
+    MetricDataQueryBuilder::default()
+        .id(metric_id)
+        .expression(expression)
+        .label(label)
+        .period(period)
+        .build()
+}
 
-    let start_time = SystemTime::now()
-        .checked_sub(Duration::from_secs(86400))
-        .unwrap();
-    let end_time = SystemTime::now();
+pub fn build_metric(
+    metric_id: &str,
+    lambda_name: &str,
+    metric_name: &str,
+    stat: &str,
+    period: i32,
+) -> MetricDataQuery {
+    build_query(
+        metric_id,
+        "AWS/Lambda",
+        vec![Dimension::builder()
+            .name("FunctionName")
+            .value(lambda_name)
+            .build()],
+        metric_name,
+        stat,
+        period,
+    )
+}
+
+fn build_queue_metric(
+    metric_id: &str,
+    queue_name: &str,
+    metric_name: &str,
+    stat: &str,
+    period: i32,
+) -> MetricDataQuery {
+    build_query(
+        metric_id,
+        "AWS/SQS",
+        vec![Dimension::builder()
+            .name("QueueName")
+            .value(queue_name)
+            .build()],
+        metric_name,
+        stat,
+        period,
+    )
+}
+
+// get the results of the metric queries for the past 24 hours
+pub async fn metric_data(
+    client: &aws_sdk_cloudwatch::Client,
+    queries: Vec<MetricDataQuery>,
+    period: i32,
+) -> Result<Vec<Metric>> {
+    shifted_metric_data(client, queries, period, Duration::ZERO).await
+}
+
+// get the results of the metric queries for the 24 hours ending `offset` ago, with the timestamps
+// moved forward by the offset so they line up with the current window
+pub async fn shifted_metric_data(
+    client: &aws_sdk_cloudwatch::Client,
+    queries: Vec<MetricDataQuery>,
+    period: i32,
+    offset: Duration,
+) -> Result<Vec<Metric>> {
+    windowed_metric_data(client, queries, period, Duration::from_secs(86400), offset).await
+}
+
+// get the results of the metric queries for the window ending `offset` ago, with every period of
+// the window present, empty periods as zero
+async fn windowed_metric_data(
+    client: &aws_sdk_cloudwatch::Client,
+    queries: Vec<MetricDataQuery>,
+    period: i32,
+    window: Duration,
+    offset: Duration,
+) -> Result<Vec<Metric>> {
+    let end_time = SystemTime::now().checked_sub(offset).unwrap();
+    let start_time = end_time.checked_sub(window).unwrap();
 
     let response = client
         .get_metric_data()
-        .metric_data_queries(build_metric(
-            "invocations",
-            arn,
-            "Invocations",
-            "Sum",
-            period,
-        ))
-        .metric_data_queries(build_metric("errors", arn, "Errors", "Sum", period))
-        .metric_data_queries(build_metric("duration", arn, "Duration", "Average", period))
-        .metric_data_queries(build_metric(
-            "concurrent_executions",
-            arn,
-            "ConcurrentExecutions",
-            "Maximum",
-            period,
-        ))
+        .set_metric_data_queries(Some(queries))
         .start_time(start_time.into())
         .end_time(end_time.into())
         .send()
@@ -101,7 +162,7 @@ pub async fn metrics(client: &aws_sdk_cloudwatch::Client, arn: &String) -> Resul
                 values: metrics.iter().map(|(_, v)| **v).collect(),
                 timestamps: metrics
                     .iter()
-                    .map(|(k, _)| (**k * period as u64) as u64)
+                    .map(|(k, _)| **k * period as u64 + offset.as_secs())
                     .collect(),
                 metric: metric.label().unwrap().to_string(),
             })
@@ -110,3 +171,550 @@ pub async fn metrics(client: &aws_sdk_cloudwatch::Client, arn: &String) -> Resul
 
     Ok(respone.into_iter().collect::<Result<Vec<Metric>>>()?)
 }
+
+// get invocations of a lambda for the past 24 hours
+pub async fn metrics(
+    client: &aws_sdk_cloudwatch::Client,
+    name: &str,
+    config: &MetricsConfig,
+) -> Result<Vec<Metric>> {
+    let period = 60;
+
+    let metrics = metric_data(client, metric_queries(name, config, period), period).await?;
+
+    Ok(with_custom_names(metrics, name, config))
+}
+
+// get the same metrics as `metrics` for the 24 hours ending `offset` ago
+pub async fn baseline_metrics(
+    client: &aws_sdk_cloudwatch::Client,
+    name: &str,
+    config: &MetricsConfig,
+    offset: Duration,
+) -> Result<Vec<Metric>> {
+    let period = 60;
+
+    let metrics =
+        shifted_metric_data(client, metric_queries(name, config, period), period, offset).await?;
+
+    Ok(with_custom_names(metrics, name, config))
+}
+
+// the custom metrics configured for a function, along with the id of their query
+fn custom_metrics<'a>(
+    name: &str,
+    config: &'a MetricsConfig,
+) -> impl Iterator<Item = (String, &'a CustomMetric)> {
+    let name = name.to_string();
+    config
+        .custom
+        .iter()
+        .enumerate()
+        .filter(move |(_, custom)| custom.applies_to(&name))
+        .map(|(index, custom)| {
+            (
+                custom.id.clone().unwrap_or(format!("custom_{}", index)),
+                custom,
+            )
+        })
+}
+
+// custom metrics are charted under their label rather than their query id
+fn with_custom_names(metrics: Vec<Metric>, name: &str, config: &MetricsConfig) -> Vec<Metric> {
+    let names = custom_metrics(name, config)
+        .filter_map(|(id, custom)| Some((id, custom.label.clone()?)))
+        .collect::<Vec<_>>();
+
+    metrics
+        .into_iter()
+        .map(
+            |metric| match names.iter().find(|(id, _)| *id == metric.name) {
+                Some((_, label)) => Metric {
+                    name: label.clone(),
+                    ..metric
+                },
+                None => metric,
+            },
+        )
+        .collect()
+}
+
+fn metric_queries(name: &str, config: &MetricsConfig, period: i32) -> Vec<MetricDataQuery> {
+    // only used by the expressions, throttled invocations are not counted as invocations
+    let mut throttles = build_metric("throttles", name, "Throttles", "Sum", period);
+    throttles.return_data = Some(false);
+
+    let mut queries = vec![
+        build_metric("invocations", name, "Invocations", "Sum", period),
+        build_metric("errors", name, "Errors", "Sum", period),
+        build_metric("duration", name, "Duration", "Average", period),
+        build_metric(
+            "concurrent_executions",
+            name,
+            "ConcurrentExecutions",
+            "Maximum",
+            period,
+        ),
+        throttles,
+        build_expression(
+            "error_rate",
+            "100 * errors / invocations",
+            "Error rate %",
+            period,
+        ),
+        build_expression(
+            "throttle_rate",
+            "100 * throttles / (invocations + throttles)",
+            "Throttle rate %",
+            period,
+        ),
+    ];
+
+    queries.extend(custom_metrics(name, config).map(|(id, custom)| {
+        build_query(
+            &id,
+            &custom.namespace,
+            custom
+                .dimensions_for(name)
+                .into_iter()
+                .map(|(name, value)| Dimension::builder().name(name).value(value).build())
+                .collect(),
+            &custom.metric_name,
+            &custom.stat,
+            period,
+        )
+    }));
+
+    queries.extend(config.expressions.iter().map(|e| {
+        build_expression(
+            &e.id,
+            &e.expression,
+            e.label.as_deref().unwrap_or(&e.id),
+            period,
+        )
+    }));
+
+    queries
+}
+
+/// GetMetricData accepts at most 500 queries per request.
+const MAX_QUERIES: usize = 500;
+
+const FLEET_METRICS: [(&str, &str, &str); 4] = [
+    ("invocations", "Invocations", "Sum"),
+    ("errors", "Errors", "Sum"),
+    ("throttles", "Throttles", "Sum"),
+    ("duration", "Duration", "p99"),
+];
+
+// the datapoints of each query over the window, as seconds since the epoch and value, following
+// the pagination of the results
+async fn window_results(
+    client: &aws_sdk_cloudwatch::Client,
+    queries: Vec<MetricDataQuery>,
+    window: Duration,
+) -> Result<std::collections::HashMap<String, Vec<(i64, f64)>>> {
+    let end_time = SystemTime::now();
+    let start_time = end_time.checked_sub(window).unwrap();
+
+    let mut results: std::collections::HashMap<String, Vec<(i64, f64)>> =
+        std::collections::HashMap::new();
+    let mut next_token = None;
+
+    loop {
+        let response = client
+            .get_metric_data()
+            .set_metric_data_queries(Some(queries.clone()))
+            .start_time(start_time.into())
+            .end_time(end_time.into())
+            .set_next_token(next_token)
+            .send()
+            .await?;
+
+        for result in response.metric_data_results() {
+            let id = result.id().unwrap_or_default().to_string();
+            results.entry(id).or_default().extend(
+                result
+                    .timestamps()
+                    .iter()
+                    .map(|t| t.secs())
+                    .zip(result.values().iter().copied()),
+            );
+        }
+
+        next_token = response.next_token().map(|t| t.to_string());
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    Ok(results)
+}
+
+// the single value of each query over the whole window, the window isn't aligned to the period
+// so its datapoints are added up for sums, but a percentile or average over the window is
+// closest to the largest of its datapoints rather than their sum
+async fn window_totals(
+    client: &aws_sdk_cloudwatch::Client,
+    queries: Vec<MetricDataQuery>,
+    window: Duration,
+) -> Result<std::collections::HashMap<String, f64>> {
+    let sums = queries
+        .iter()
+        .filter(|query| {
+            query
+                .metric_stat()
+                .and_then(|metric_stat| metric_stat.stat())
+                == Some("Sum")
+        })
+        .filter_map(|query| query.id().map(|id| id.to_string()))
+        .collect::<std::collections::HashSet<_>>();
+
+    Ok(window_results(client, queries, window)
+        .await?
+        .into_iter()
+        .map(|(id, datapoints)| {
+            let values = datapoints.iter().map(|(_, v)| *v);
+            let total = if sums.contains(&id) {
+                values.sum()
+            } else {
+                values.fold(0.0, f64::max)
+            };
+            (id, total)
+        })
+        .collect())
+}
+
+// get the totals of every function over the window, batching as many functions as fit into
+// each request
+pub async fn fleet_metrics(
+    client: &aws_sdk_cloudwatch::Client,
+    lambdas: Vec<Lambda>,
+    window: Duration,
+) -> Result<Vec<FleetMetrics>> {
+    let period = window.as_secs() as i32;
+
+    let batches = lambdas
+        .chunks(MAX_QUERIES / FLEET_METRICS.len())
+        .map(|batch| batch.to_vec())
+        .collect::<Vec<_>>();
+
+    let results = futures::future::try_join_all(batches.into_iter().map(|batch| async move {
+        let queries = batch
+            .iter()
+            .enumerate()
+            .flat_map(|(index, lambda)| {
+                FLEET_METRICS.iter().map(move |(id, metric_name, stat)| {
+                    build_metric(
+                        &format!("f{}_{}", index, id),
+                        &lambda.name,
+                        metric_name,
+                        stat,
+                        period,
+                    )
+                })
+            })
+            .collect();
+
+        let totals = window_totals(client, queries, window).await?;
+        let total = |index: usize, id: &str| {
+            totals
+                .get(&format!("f{}_{}", index, id))
+                .copied()
+                .unwrap_or(0.0)
+        };
+
+        Ok::<_, anyhow::Error>(
+            batch
+                .into_iter()
+                .enumerate()
+                .map(|(index, lambda)| FleetMetrics {
+                    invocations: total(index, "invocations"),
+                    errors: total(index, "errors"),
+                    throttles: total(index, "throttles"),
+                    p99_duration: total(index, "duration"),
+                    lambda,
+                })
+                .collect::<Vec<_>>(),
+        )
+    }))
+    .await?;
+
+    Ok(results.into_iter().flatten().collect())
+}
+
+// get the invocations of each function in periods over the window, oldest first, and its errors
+// over the whole window
+pub async fn recent_activity(
+    client: &aws_sdk_cloudwatch::Client,
+    lambdas: Vec<Lambda>,
+    window: Duration,
+    period: i32,
+) -> Result<Vec<(Lambda, RecentActivity)>> {
+    let buckets = (window.as_secs() / period as u64) as usize;
+
+    let batches = lambdas
+        .chunks(MAX_QUERIES / 2)
+        .map(|batch| batch.to_vec())
+        .collect::<Vec<_>>();
+
+    let results = futures::future::try_join_all(batches.into_iter().map(|batch| async move {
+        let queries = batch
+            .iter()
+            .enumerate()
+            .flat_map(|(index, lambda)| {
+                [
+                    build_metric(
+                        &format!("f{}_invocations", index),
+                        &lambda.name,
+                        "Invocations",
+                        "Sum",
+                        period,
+                    ),
+                    build_metric(
+                        &format!("f{}_errors", index),
+                        &lambda.name,
+                        "Errors",
+                        "Sum",
+                        period,
+                    ),
+                ]
+            })
+            .collect();
+
+        let results = window_results(client, queries, window).await?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+
+        Ok::<_, anyhow::Error>(
+            batch
+                .into_iter()
+                .enumerate()
+                .map(|(index, lambda)| {
+                    let mut invocations = vec![0.0; buckets];
+                    for (timestamp, value) in results
+                        .get(&format!("f{}_invocations", index))
+                        .into_iter()
+                        .flatten()
+                    {
+                        let age = ((now - timestamp).max(0) / period as i64) as usize;
+                        if age < buckets {
+                            invocations[buckets - 1 - age] += value;
+                        }
+                    }
+
+                    let errors = results
+                        .get(&format!("f{}_errors", index))
+                        .into_iter()
+                        .flatten()
+                        .map(|(_, v)| v)
+                        .sum();
+
+                    (
+                        lambda,
+                        RecentActivity {
+                            invocations,
+                            errors,
+                        },
+                    )
+                })
+                .collect::<Vec<_>>(),
+        )
+    }))
+    .await?;
+
+    Ok(results.into_iter().flatten().collect())
+}
+
+// get the invocation metrics of two functions in a single request, so both cover the same window
+pub async fn comparison_metrics(
+    client: &aws_sdk_cloudwatch::Client,
+    left: &str,
+    right: &str,
+) -> Result<Vec<(Metric, Metric)>> {
+    let period = 60;
+    let metrics = [
+        ("invocations", "Invocations", "Sum"),
+        ("errors", "Errors", "Sum"),
+        ("duration", "Duration", "Average"),
+        ("concurrent_executions", "ConcurrentExecutions", "Maximum"),
+    ];
+
+    let queries = metrics
+        .iter()
+        .flat_map(|(id, metric_name, stat)| {
+            [
+                build_metric(&format!("left_{}", id), left, metric_name, stat, period),
+                build_metric(&format!("right_{}", id), right, metric_name, stat, period),
+            ]
+        })
+        .collect();
+
+    let results = metric_data(client, queries, period).await?;
+
+    let find = |id: String| {
+        results.iter().find(|m| m.name == id).map(|m| Metric {
+            name: id.split_once('_').unwrap().1.to_string(),
+            ..m.clone()
+        })
+    };
+
+    Ok(metrics
+        .iter()
+        .filter_map(|(id, _, _)| {
+            Some((
+                find(format!("left_{}", id))?,
+                find(format!("right_{}", id))?,
+            ))
+        })
+        .collect())
+}
+
+// get the invocations and errors of a lambda for the past hour
+pub async fn preview_metrics(
+    client: &aws_sdk_cloudwatch::Client,
+    name: &str,
+) -> Result<Vec<Metric>> {
+    let period = 60;
+
+    windowed_metric_data(
+        client,
+        vec![
+            build_metric("invocations", name, "Invocations", "Sum", period),
+            build_metric("errors", name, "Errors", "Sum", period),
+        ],
+        period,
+        Duration::from_secs(3600),
+        Duration::ZERO,
+    )
+    .await
+}
+
+// get the asynchronous invocation metrics of a lambda for the past 24 hours
+pub async fn async_metrics(client: &aws_sdk_cloudwatch::Client, name: &str) -> Result<Vec<Metric>> {
+    let period = 60;
+
+    metric_data(
+        client,
+        vec![
+            build_metric(
+                "destination_delivery_failures",
+                name,
+                "DestinationDeliveryFailures",
+                "Sum",
+                period,
+            ),
+            build_metric(
+                "dead_letter_errors",
+                name,
+                "DeadLetterErrors",
+                "Sum",
+                period,
+            ),
+            build_metric(
+                "async_events_dropped",
+                name,
+                "AsyncEventsDropped",
+                "Sum",
+                period,
+            ),
+        ],
+        period,
+    )
+    .await
+}
+
+// get the health metrics of an sqs queue for the past 24 hours
+pub async fn queue_metrics(
+    client: &aws_sdk_cloudwatch::Client,
+    queue_name: &str,
+) -> Result<Vec<Metric>> {
+    let period = 60;
+
+    metric_data(
+        client,
+        vec![
+            build_queue_metric(
+                "messages_visible",
+                queue_name,
+                "ApproximateNumberOfMessagesVisible",
+                "Maximum",
+                period,
+            ),
+            build_queue_metric(
+                "age_of_oldest_message",
+                queue_name,
+                "ApproximateAgeOfOldestMessage",
+                "Maximum",
+                period,
+            ),
+            build_queue_metric(
+                "messages_sent",
+                queue_name,
+                "NumberOfMessagesSent",
+                "Sum",
+                period,
+            ),
+            build_queue_metric(
+                "messages_deleted",
+                queue_name,
+                "NumberOfMessagesDeleted",
+                "Sum",
+                period,
+            ),
+        ],
+        period,
+    )
+    .await
+}
+
+// get the invocation metrics of a version, or of a version invoked through an alias
+pub async fn version_metrics(
+    client: &aws_sdk_cloudwatch::Client,
+    lambda_name: &str,
+    resource: &str,
+    executed_version: Option<&str>,
+) -> Result<Vec<Metric>> {
+    let period = 60;
+
+    let mut dimensions = vec![
+        Dimension::builder()
+            .name("FunctionName")
+            .value(lambda_name)
+            .build(),
+        Dimension::builder()
+            .name("Resource")
+            .value(resource)
+            .build(),
+    ];
+
+    if let Some(executed_version) = executed_version {
+        dimensions.push(
+            Dimension::builder()
+                .name("ExecutedVersion")
+                .value(executed_version)
+                .build(),
+        );
+    }
+
+    let query = |metric_id: &str, metric_name: &str, stat: &str| {
+        build_query(
+            metric_id,
+            "AWS/Lambda",
+            dimensions.clone(),
+            metric_name,
+            stat,
+            period,
+        )
+    };
+
+    metric_data(
+        client,
+        vec![
+            query("invocations", "Invocations", "Sum"),
+            query("errors", "Errors", "Sum"),
+            query("duration", "Duration", "Average"),
+        ],
+        period,
+    )
+    .await
+}
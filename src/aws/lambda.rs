@@ -1,7 +1,28 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 
-use crate::core::{EventSourceMapping, EventSourceMappingState, Lambda};
-use std::{fs, io::Write, path::Path};
+use crate::core::{
+    Alias, AsyncConfig, Concurrency, EventSourceMapping, EventSourceMappingState,
+    FunctionConfiguration, InvocationResult, Lambda, ProvisionedConcurrency, Version,
+};
+use aws_sdk_lambda::{
+    primitives::Blob,
+    types::{AliasRoutingConfiguration, Environment, EphemeralStorage, LastUpdateStatus},
+};
+use futures::StreamExt;
+use std::{collections::HashMap, fs, io::Write, path::Path};
+
+/// How many functions have their tags fetched at once when the function list is refreshed.
+const TAG_CONCURRENCY: usize = 8;
+
+/// Bumped whenever `Lambda` gains attributes, so older caches are fetched again rather than
+/// searched without them.
+const CACHE_VERSION: u32 = 2;
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct LambdaCache {
+    version: u32,
+    lambdas: Vec<Lambda>,
+}
 
 fn read_lambdas_from_cache() -> Option<Vec<Lambda>> {
     let path = format!(
@@ -14,12 +35,15 @@ fn read_lambdas_from_cache() -> Option<Vec<Lambda>> {
     }
 
     let content = fs::read_to_string(&path).unwrap();
-    let lambda_functions: Vec<Lambda> = serde_json::from_str(&content).unwrap();
 
-    Some(lambda_functions)
+    // caches from older versions, including the plain list of functions, are fetched again
+    serde_json::from_str::<LambdaCache>(&content)
+        .ok()
+        .filter(|cache| cache.version == CACHE_VERSION)
+        .map(|cache| cache.lambdas)
 }
 
-fn write_lambdas_to_cache(lambda_functions: &Vec<Lambda>) {
+fn write_lambdas_to_cache(lambda_functions: &[Lambda]) {
     let path = format!(
         "{}/.config/shepherd/lambdas.json",
         dirs::home_dir().unwrap().to_str().unwrap()
@@ -29,7 +53,11 @@ fn write_lambdas_to_cache(lambda_functions: &Vec<Lambda>) {
         fs::create_dir_all(cache).unwrap();
     }
 
-    let content = serde_json::to_string(lambda_functions).unwrap();
+    let content = serde_json::to_string(&LambdaCache {
+        version: CACHE_VERSION,
+        lambdas: lambda_functions.to_vec(),
+    })
+    .unwrap();
 
     let mut file = fs::File::create(&path).unwrap();
 
@@ -54,6 +82,31 @@ async fn fetch_lambdas(client: &aws_sdk_lambda::Client) -> Result<Vec<Lambda>> {
             memory: f.memory_size.unwrap() as i64,
             name: f.function_name.clone().unwrap(),
             arn: f.function_arn.clone().unwrap(),
+            architecture: f
+                .architectures()
+                .first()
+                .map(|a| a.as_str().to_string())
+                .unwrap_or("x86_64".to_string()),
+            layers: f
+                .layers()
+                .iter()
+                .flat_map(|l| l.arn().map(|arn| arn.to_string()))
+                .collect(),
+            tags: HashMap::new(),
+            triggers: vec![],
+            trigger_names: vec![],
+            environment_keys: f
+                .environment()
+                .and_then(|e| e.variables())
+                .map(|variables| {
+                    let mut keys = variables.keys().cloned().collect::<Vec<_>>();
+                    keys.sort();
+                    keys
+                })
+                .unwrap_or_default(),
+            last_modified: f.last_modified().unwrap_or_default().to_string(),
+            code_size: f.code_size,
+            description: f.description().unwrap_or_default().to_string(),
         });
 
         lambda_functions.extend(functions);
@@ -64,9 +117,83 @@ async fn fetch_lambdas(client: &aws_sdk_lambda::Client) -> Result<Vec<Lambda>> {
         }
     }
 
+    // tags and triggers only enrich the list for filtering and grouping, so the list is shown
+    // without them when they cannot be fetched
+    let mut triggers = account_triggers(client).await.unwrap_or_default();
+
+    // tags are not part of the function list, so they are fetched per function, stopping at the
+    // first failure since a missing permission or throttling would fail every other call too
+    let mut tags =
+        futures::stream::iter(lambda_functions.iter().map(|l| lambda_tags(client, &l.arn)))
+            .buffered(TAG_CONCURRENCY)
+            .take_while(|tags| futures::future::ready(tags.is_ok()))
+            .filter_map(|tags| futures::future::ready(tags.ok()))
+            .collect::<Vec<_>>()
+            .await
+            .into_iter();
+
+    for lambda in lambda_functions.iter_mut() {
+        lambda.tags = tags.next().unwrap_or_default();
+        for (service, name) in triggers.remove(&lambda.arn).unwrap_or_default() {
+            if !lambda.triggers.contains(&service) {
+                lambda.triggers.push(service);
+            }
+            lambda.trigger_names.push(name);
+        }
+    }
+
     Ok(lambda_functions)
 }
 
+// the services and names of the event sources mapped in the account, by function arn
+async fn account_triggers(
+    client: &aws_sdk_lambda::Client,
+) -> Result<HashMap<String, Vec<(String, String)>>> {
+    let mut triggers: HashMap<String, Vec<(String, String)>> = HashMap::new();
+    let mut next_marker = None;
+
+    loop {
+        let response = client
+            .list_event_source_mappings()
+            .set_marker(next_marker)
+            .send()
+            .await?;
+
+        for mapping in response.event_source_mappings() {
+            if let (Some(function_arn), Some(source_arn)) =
+                (mapping.function_arn(), mapping.event_source_arn())
+            {
+                let parts = source_arn.split(':').collect::<Vec<_>>();
+                let service = parts.get(2).copied().unwrap_or_default().to_string();
+                // the queue, table or stream name, from resources such as table/<name>/stream/<time>
+                let name = parts
+                    .last()
+                    .map(|resource| resource.split('/').nth(1).unwrap_or(resource))
+                    .unwrap_or_default()
+                    .to_string();
+
+                // mappings on an alias or version count towards the function
+                let function_arn = function_arn
+                    .split(':')
+                    .take(7)
+                    .collect::<Vec<_>>()
+                    .join(":");
+                triggers
+                    .entry(function_arn)
+                    .or_default()
+                    .push((service, name));
+            }
+        }
+
+        next_marker = response.next_marker().map(|m| m.to_string());
+        if next_marker.is_none() {
+            break;
+        }
+    }
+
+    Ok(triggers)
+}
+
 pub(crate) fn clear_cache() {
     let path = format!(
         "{}/.config/shepherd/lambdas.json",
@@ -120,6 +247,7 @@ pub(crate) async fn lambda_event_source_mappings(
         match event_source.as_ref() {
             "sqs" => Some(EventSourceMapping::SQS {
                 name,
+                arn: m.event_source_arn().unwrap().to_string(),
                 batch_size: m.batch_size.unwrap() as i64,
                 batch_window: m.maximum_batching_window_in_seconds().unwrap() as i64,
                 state: match m.state().unwrap().as_ref() {
@@ -145,3 +273,477 @@ pub(crate) async fn lambda_event_source_mappings(
 
     Ok(mappings.collect())
 }
+
+// an arn targets the lambda if it is the function arn, or a qualified version of it
+pub(crate) fn is_lambda_arn(arn: &str, lambda_arn: &str) -> bool {
+    arn == lambda_arn || arn.starts_with(&format!("{}:", lambda_arn))
+}
+
+pub(crate) async fn lambda_policy(
+    client: &aws_sdk_lambda::Client,
+    lambda_name: &str,
+) -> Result<Option<String>> {
+    let response = client.get_policy().function_name(lambda_name).send().await;
+
+    match response {
+        Ok(response) => Ok(response.policy().map(|p| p.to_string())),
+        // functions without any permissions have no policy at all
+        Err(e)
+            if e.as_service_error()
+                .map(|e| e.is_resource_not_found_exception())
+                .unwrap_or(false) =>
+        {
+            Ok(None)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub(crate) async fn lambda_function_url(
+    client: &aws_sdk_lambda::Client,
+    lambda_name: &str,
+) -> Result<Option<(String, String)>> {
+    let response = client
+        .get_function_url_config()
+        .function_name(lambda_name)
+        .send()
+        .await;
+
+    match response {
+        Ok(response) => Ok(Some((
+            response.function_url().to_string(),
+            response.auth_type().as_str().to_string(),
+        ))),
+        Err(e)
+            if e.as_service_error()
+                .map(|e| e.is_resource_not_found_exception())
+                .unwrap_or(false) =>
+        {
+            Ok(None)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub(crate) async fn lambda_async_config(
+    client: &aws_sdk_lambda::Client,
+    lambda_name: &str,
+) -> Result<AsyncConfig> {
+    let configuration = client
+        .get_function_configuration()
+        .function_name(lambda_name)
+        .send()
+        .await?;
+
+    let dead_letter_target = configuration
+        .dead_letter_config()
+        .and_then(|c| c.target_arn())
+        .map(|arn| arn.to_string());
+
+    let response = client
+        .get_function_event_invoke_config()
+        .function_name(lambda_name)
+        .send()
+        .await;
+
+    match response {
+        Ok(response) => {
+            let destinations = response.destination_config();
+            Ok(AsyncConfig {
+                maximum_retry_attempts: response.maximum_retry_attempts().map(|x| x as i64),
+                maximum_event_age_in_seconds: response
+                    .maximum_event_age_in_seconds()
+                    .map(|x| x as i64),
+                on_success: destinations
+                    .and_then(|d| d.on_success())
+                    .and_then(|d| d.destination())
+                    .map(|d| d.to_string()),
+                on_failure: destinations
+                    .and_then(|d| d.on_failure())
+                    .and_then(|d| d.destination())
+                    .map(|d| d.to_string()),
+                dead_letter_target,
+            })
+        }
+        // without an event invoke config the lambda uses the default retry behaviour
+        Err(e)
+            if e.as_service_error()
+                .map(|e| e.is_resource_not_found_exception())
+                .unwrap_or(false) =>
+        {
+            Ok(AsyncConfig {
+                dead_letter_target,
+                ..AsyncConfig::default()
+            })
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub(crate) async fn invoke_lambda(
+    client: &aws_sdk_lambda::Client,
+    lambda_name: &str,
+    payload: &str,
+) -> Result<InvocationResult> {
+    let response = client
+        .invoke()
+        .function_name(lambda_name)
+        .payload(Blob::new(payload.as_bytes()))
+        .send()
+        .await?;
+
+    Ok(InvocationResult {
+        status_code: response.status_code() as i64,
+        function_error: response.function_error().map(|e| e.to_string()),
+        payload: response
+            .payload()
+            .map(|p| String::from_utf8_lossy(p.as_ref()).to_string())
+            .unwrap_or_default(),
+    })
+}
+
+pub(crate) async fn lambda_concurrency(
+    client: &aws_sdk_lambda::Client,
+    lambda_name: &str,
+) -> Result<Concurrency> {
+    let reserved = client
+        .get_function_concurrency()
+        .function_name(lambda_name)
+        .send()
+        .await?
+        .reserved_concurrent_executions()
+        .map(|x| x as i64);
+
+    let mut provisioned = Vec::new();
+    let mut next_marker = None;
+    loop {
+        let response = client
+            .list_provisioned_concurrency_configs()
+            .function_name(lambda_name)
+            .set_marker(next_marker)
+            .send()
+            .await?;
+
+        provisioned.extend(response.provisioned_concurrency_configs().iter().map(|c| {
+            ProvisionedConcurrency {
+                qualifier: c
+                    .function_arn()
+                    .and_then(|arn| arn.rsplit(':').next())
+                    .unwrap_or_default()
+                    .to_string(),
+                requested: c.requested_provisioned_concurrent_executions().unwrap_or(0) as i64,
+                allocated: c.allocated_provisioned_concurrent_executions().unwrap_or(0) as i64,
+                available: c.available_provisioned_concurrent_executions().unwrap_or(0) as i64,
+                status: c
+                    .status()
+                    .map(|s| s.as_str().to_string())
+                    .unwrap_or_default(),
+                status_reason: c.status_reason().map(|r| r.to_string()),
+            }
+        }));
+
+        next_marker = response.next_marker().map(|s| s.to_string());
+        if next_marker.is_none() {
+            break;
+        }
+    }
+
+    let account_settings = client.get_account_settings().send().await?;
+    let account_limit = account_settings.account_limit();
+
+    Ok(Concurrency {
+        reserved,
+        provisioned,
+        account_limit: account_limit
+            .map(|l| l.concurrent_executions() as i64)
+            .unwrap_or(0),
+        unreserved: account_limit
+            .and_then(|l| l.unreserved_concurrent_executions())
+            .unwrap_or(0) as i64,
+    })
+}
+
+// the api takes an i32, a larger value is refused rather than wrapped around
+fn api_value(setting: &str, value: i64) -> Result<i32> {
+    i32::try_from(value).map_err(|_| anyhow!("{} {} is out of range", setting, value))
+}
+
+pub(crate) async fn set_reserved_concurrency(
+    client: &aws_sdk_lambda::Client,
+    lambda_name: &str,
+    reserved: Option<i64>,
+) -> Result<()> {
+    match reserved {
+        Some(reserved) => {
+            client
+                .put_function_concurrency()
+                .function_name(lambda_name)
+                .reserved_concurrent_executions(api_value("concurrency", reserved)?)
+                .send()
+                .await?;
+        }
+        None => {
+            client
+                .delete_function_concurrency()
+                .function_name(lambda_name)
+                .send()
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+// setting provisioned concurrency to zero removes the config from the alias or version
+pub(crate) async fn set_provisioned_concurrency(
+    client: &aws_sdk_lambda::Client,
+    lambda_name: &str,
+    qualifier: &str,
+    provisioned: i64,
+) -> Result<()> {
+    if provisioned > 0 {
+        client
+            .put_provisioned_concurrency_config()
+            .function_name(lambda_name)
+            .qualifier(qualifier)
+            .provisioned_concurrent_executions(api_value("concurrency", provisioned)?)
+            .send()
+            .await?;
+    } else {
+        let response = client
+            .delete_provisioned_concurrency_config()
+            .function_name(lambda_name)
+            .qualifier(qualifier)
+            .send()
+            .await;
+
+        match response {
+            Ok(_) => {}
+            // nothing was provisioned, which is what was asked for
+            Err(e)
+                if e.as_service_error()
+                    .map(|e| e.is_resource_not_found_exception())
+                    .unwrap_or(false) => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(())
+}
+
+pub(crate) async fn lambda_versions(
+    client: &aws_sdk_lambda::Client,
+    lambda_name: &str,
+) -> Result<Vec<Version>> {
+    let mut versions = Vec::new();
+    let mut next_marker = None;
+    loop {
+        let response = client
+            .list_versions_by_function()
+            .function_name(lambda_name)
+            .set_marker(next_marker)
+            .send()
+            .await?;
+
+        versions.extend(response.versions().iter().map(|v| Version {
+            version: v.version().unwrap_or_default().to_string(),
+            description: v.description().unwrap_or_default().to_string(),
+            code_sha256: v.code_sha256().unwrap_or_default().to_string(),
+            last_modified: v.last_modified().unwrap_or_default().to_string(),
+        }));
+
+        next_marker = response.next_marker().map(|s| s.to_string());
+        if next_marker.is_none() {
+            break;
+        }
+    }
+
+    // newest versions first, $LATEST always sorts to the top
+    versions.sort_by_key(|v| std::cmp::Reverse(v.version.parse::<i64>().unwrap_or(i64::MAX)));
+
+    Ok(versions)
+}
+
+fn routing_weights(routing_config: Option<&AliasRoutingConfiguration>) -> Vec<(String, f64)> {
+    let mut weights = routing_config
+        .and_then(|r| r.additional_version_weights())
+        .map(|weights| {
+            weights
+                .iter()
+                .map(|(version, weight)| (version.clone(), *weight))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    weights.sort_by(|a, b| a.0.cmp(&b.0));
+    weights
+}
+
+pub(crate) async fn lambda_aliases(
+    client: &aws_sdk_lambda::Client,
+    lambda_name: &str,
+) -> Result<Vec<Alias>> {
+    let mut aliases = Vec::new();
+    let mut next_marker = None;
+    loop {
+        let response = client
+            .list_aliases()
+            .function_name(lambda_name)
+            .set_marker(next_marker)
+            .send()
+            .await?;
+
+        aliases.extend(response.aliases().iter().map(|a| Alias {
+            name: a.name().unwrap_or_default().to_string(),
+            function_version: a.function_version().unwrap_or_default().to_string(),
+            description: a.description().unwrap_or_default().to_string(),
+            additional_version_weights: routing_weights(a.routing_config()),
+            previous_version: None,
+        }));
+
+        next_marker = response.next_marker().map(|s| s.to_string());
+        if next_marker.is_none() {
+            break;
+        }
+    }
+
+    Ok(aliases)
+}
+
+// point the alias at a version, with any additional versions receiving a share of the traffic
+pub(crate) async fn update_alias(
+    client: &aws_sdk_lambda::Client,
+    lambda_name: &str,
+    alias: &Alias,
+) -> Result<Alias> {
+    let routing_config = AliasRoutingConfiguration::builder()
+        .set_additional_version_weights(Some(
+            alias
+                .additional_version_weights
+                .iter()
+                .cloned()
+                .collect::<HashMap<String, f64>>(),
+        ))
+        .build();
+
+    let response = client
+        .update_alias()
+        .function_name(lambda_name)
+        .name(&alias.name)
+        .function_version(&alias.function_version)
+        .routing_config(routing_config)
+        .send()
+        .await?;
+
+    Ok(Alias {
+        name: response.name().unwrap_or_default().to_string(),
+        function_version: response.function_version().unwrap_or_default().to_string(),
+        description: response.description().unwrap_or_default().to_string(),
+        additional_version_weights: routing_weights(response.routing_config()),
+        previous_version: None,
+    })
+}
+
+pub(crate) async fn lambda_configuration(
+    client: &aws_sdk_lambda::Client,
+    lambda_name: &str,
+    qualifier: Option<&str>,
+) -> Result<FunctionConfiguration> {
+    let response = client
+        .get_function_configuration()
+        .function_name(lambda_name)
+        .set_qualifier(qualifier.map(|q| q.to_string()))
+        .send()
+        .await?;
+
+    let mut environment = response
+        .environment()
+        .and_then(|e| e.variables())
+        .map(|variables| {
+            variables
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    environment.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(FunctionConfiguration {
+        memory: response.memory_size().unwrap_or_default() as i64,
+        timeout: response.timeout().unwrap_or_default() as i64,
+        ephemeral_storage: response
+            .ephemeral_storage()
+            .map(|e| e.size() as i64)
+            .unwrap_or(512),
+        environment,
+        runtime: response
+            .runtime()
+            .map(|r| r.to_string())
+            .unwrap_or_default(),
+        role: response.role().unwrap_or_default().to_string(),
+        layers: response
+            .layers()
+            .iter()
+            .filter_map(|l| l.arn().map(|arn| arn.to_string()))
+            .collect(),
+        subnet_ids: response
+            .vpc_config()
+            .map(|v| v.subnet_ids().to_vec())
+            .unwrap_or_default(),
+        security_group_ids: response
+            .vpc_config()
+            .map(|v| v.security_group_ids().to_vec())
+            .unwrap_or_default(),
+    })
+}
+
+pub(crate) async fn update_configuration(
+    client: &aws_sdk_lambda::Client,
+    lambda_name: &str,
+    config: &FunctionConfiguration,
+) -> Result<()> {
+    client
+        .update_function_configuration()
+        .function_name(lambda_name)
+        .memory_size(api_value("memory", config.memory)?)
+        .timeout(api_value("timeout", config.timeout)?)
+        .ephemeral_storage(
+            EphemeralStorage::builder()
+                .size(api_value("ephemeral storage", config.ephemeral_storage)?)
+                .build()?,
+        )
+        .environment(
+            Environment::builder()
+                .set_variables(Some(config.environment.iter().cloned().collect()))
+                .build(),
+        )
+        .send()
+        .await?;
+
+    Ok(())
+}
+
+/// The status of the last configuration update, along with the reason it failed if it did.
+pub(crate) async fn last_update_status(
+    client: &aws_sdk_lambda::Client,
+    lambda_name: &str,
+) -> Result<(LastUpdateStatus, Option<String>)> {
+    let response = client
+        .get_function_configuration()
+        .function_name(lambda_name)
+        .send()
+        .await?;
+
+    Ok((
+        response
+            .last_update_status()
+            .cloned()
+            .unwrap_or(LastUpdateStatus::Successful),
+        response.last_update_status_reason().map(|r| r.to_string()),
+    ))
+}
+
+pub(crate) async fn lambda_tags(
+    client: &aws_sdk_lambda::Client,
+    lambda_arn: &str,
+) -> Result<HashMap<String, String>> {
+    let response = client.list_tags().resource(lambda_arn).send().await?;
+    Ok(response.tags().cloned().unwrap_or_default())
+}
@@ -1,17 +1,59 @@
 use aws_config::BehaviorVersion;
 
-use crate::core::{EventSourceMapping, Lambda, Metric};
+use crate::{
+    config::MetricsConfig,
+    core::{
+        Alias, AsyncConfig, Baseline, Concurrency, EventSourceMapping, EventSourceMappingState,
+        FleetMetrics, FleetWindow, FunctionConfiguration, FunctionSnapshot, InvocationResult,
+        Lambda, Metric, Preview, QualifiedLambda, QueueHealth, QueueMessage, RecentActivity,
+        Version, VersionMetrics,
+    },
+};
 use anyhow::Result;
 
+use self::resource_policy::PolicyTrigger;
+
 pub(crate) mod cloudwatch;
 pub(crate) mod event_bridge;
 pub(crate) mod lambda;
+pub(crate) mod resource_policy;
+pub(crate) mod s3;
+pub(crate) mod sns;
+pub(crate) mod sqs;
+
+fn function_snapshot(
+    function: &QualifiedLambda,
+    config: FunctionConfiguration,
+    concurrency: &Concurrency,
+    event_source_mappings: Vec<EventSourceMapping>,
+) -> FunctionSnapshot {
+    let provisioned_concurrency = function.qualifier.as_ref().and_then(|qualifier| {
+        concurrency
+            .provisioned
+            .iter()
+            .find(|p| &p.qualifier == qualifier)
+            .map(|p| p.requested)
+    });
+
+    FunctionSnapshot {
+        function: function.clone(),
+        config,
+        reserved_concurrency: concurrency.reserved,
+        provisioned_concurrency,
+        event_source_mappings,
+    }
+}
 
+// the clients are cheap to clone, so work can be moved onto its own task
+#[derive(Clone)]
 pub struct AWS {
     pub sdk_config: aws_config::SdkConfig,
     pub lambda_client: aws_sdk_lambda::Client,
     pub cw_client: aws_sdk_cloudwatch::Client,
     pub eb_client: aws_sdk_eventbridge::Client,
+    pub s3_client: aws_sdk_s3::Client,
+    pub sns_client: aws_sdk_sns::Client,
+    pub sqs_client: aws_sdk_sqs::Client,
 }
 
 impl AWS {
@@ -23,12 +65,18 @@ impl AWS {
         let cw_client = aws_sdk_cloudwatch::Client::new(&sdk_config);
         let lambda_client = aws_sdk_lambda::Client::new(&sdk_config);
         let eb_client = aws_sdk_eventbridge::Client::new(&sdk_config);
+        let s3_client = aws_sdk_s3::Client::new(&sdk_config);
+        let sns_client = aws_sdk_sns::Client::new(&sdk_config);
+        let sqs_client = aws_sdk_sqs::Client::new(&sdk_config);
 
         Self {
             sdk_config,
             lambda_client,
             cw_client,
             eb_client,
+            s3_client,
+            sns_client,
+            sqs_client,
         }
     }
 
@@ -36,8 +84,229 @@ impl AWS {
         lambda::lambda_functions(&self.lambda_client).await
     }
 
-    pub async fn metrics(&self, lambda: &Lambda) -> Result<Vec<Metric>> {
-        cloudwatch::metrics(&self.cw_client, &lambda.name).await
+    pub async fn metrics(&self, lambda: &Lambda, config: &MetricsConfig) -> Result<Vec<Metric>> {
+        cloudwatch::metrics(&self.cw_client, &lambda.name, config).await
+    }
+
+    pub async fn fleet_metrics(&self, window: FleetWindow) -> Result<Vec<FleetMetrics>> {
+        let lambdas = self.lambda_functions().await?;
+        cloudwatch::fleet_metrics(
+            &self.cw_client,
+            lambdas,
+            std::time::Duration::from_secs(window.seconds()),
+        )
+        .await
+    }
+
+    // the last 3 hours of invocations in 15 minute periods
+    pub async fn recent_activity(
+        &self,
+        lambdas: Vec<Lambda>,
+    ) -> Result<Vec<(Lambda, RecentActivity)>> {
+        cloudwatch::recent_activity(
+            &self.cw_client,
+            lambdas,
+            std::time::Duration::from_secs(3 * 3600),
+            900,
+        )
+        .await
+    }
+
+    pub async fn preview(&self, lambda: Lambda) -> Result<Preview> {
+        let (event_source_mappings, metrics) = tokio::try_join!(
+            self.event_source_mappings(&lambda),
+            cloudwatch::preview_metrics(&self.cw_client, &lambda.name),
+        )?;
+
+        Ok(Preview {
+            lambda,
+            event_source_mappings,
+            metrics,
+            error: None,
+        })
+    }
+
+    pub async fn comparison_metrics(
+        &self,
+        left: &Lambda,
+        right: &Lambda,
+    ) -> Result<Vec<(Metric, Metric)>> {
+        cloudwatch::comparison_metrics(&self.cw_client, &left.name, &right.name).await
+    }
+
+    pub async fn baseline_metrics(
+        &self,
+        lambda: &Lambda,
+        config: &MetricsConfig,
+        baseline: Baseline,
+    ) -> Result<Vec<Metric>> {
+        cloudwatch::baseline_metrics(&self.cw_client, &lambda.name, config, baseline.offset()).await
+    }
+
+    pub async fn async_config(&self, lambda: &Lambda) -> Result<AsyncConfig> {
+        lambda::lambda_async_config(&self.lambda_client, &lambda.name).await
+    }
+
+    pub async fn async_metrics(&self, lambda: &Lambda) -> Result<Vec<Metric>> {
+        cloudwatch::async_metrics(&self.cw_client, &lambda.name).await
+    }
+
+    pub async fn concurrency(&self, lambda: &Lambda) -> Result<Concurrency> {
+        lambda::lambda_concurrency(&self.lambda_client, &lambda.name).await
+    }
+
+    pub async fn set_reserved_concurrency(
+        &self,
+        lambda: &Lambda,
+        reserved: Option<i64>,
+    ) -> Result<()> {
+        lambda::set_reserved_concurrency(&self.lambda_client, &lambda.name, reserved).await
+    }
+
+    pub async fn set_provisioned_concurrency(
+        &self,
+        lambda: &Lambda,
+        qualifier: &str,
+        provisioned: i64,
+    ) -> Result<()> {
+        lambda::set_provisioned_concurrency(
+            &self.lambda_client,
+            &lambda.name,
+            qualifier,
+            provisioned,
+        )
+        .await
+    }
+
+    pub async fn configuration(&self, lambda: &Lambda) -> Result<FunctionConfiguration> {
+        lambda::lambda_configuration(&self.lambda_client, &lambda.name, None).await
+    }
+
+    // the configuration, concurrency and triggers of a function, version or alias
+    pub async fn snapshot(&self, function: &QualifiedLambda) -> Result<FunctionSnapshot> {
+        let (config, concurrency, event_source_mappings) = tokio::try_join!(
+            lambda::lambda_configuration(
+                &self.lambda_client,
+                &function.lambda.name,
+                function.qualifier.as_deref(),
+            ),
+            self.concurrency(&function.lambda),
+            self.event_source_mappings(&function.lambda),
+        )?;
+
+        Ok(function_snapshot(
+            function,
+            config,
+            &concurrency,
+            event_source_mappings,
+        ))
+    }
+
+    // a snapshot reusing the concurrency and triggers already loaded for the function, so only
+    // the configuration of the version or alias is fetched
+    pub async fn snapshot_with(
+        &self,
+        function: &QualifiedLambda,
+        concurrency: &Concurrency,
+        event_source_mappings: &[EventSourceMapping],
+    ) -> Result<FunctionSnapshot> {
+        let config = lambda::lambda_configuration(
+            &self.lambda_client,
+            &function.lambda.name,
+            function.qualifier.as_deref(),
+        )
+        .await?;
+
+        Ok(function_snapshot(
+            function,
+            config,
+            concurrency,
+            event_source_mappings.to_vec(),
+        ))
+    }
+
+    pub async fn update_configuration(
+        &self,
+        lambda: &Lambda,
+        config: &FunctionConfiguration,
+    ) -> Result<()> {
+        lambda::update_configuration(&self.lambda_client, &lambda.name, config).await
+    }
+
+    pub async fn last_update_status(
+        &self,
+        lambda: &Lambda,
+    ) -> Result<(aws_sdk_lambda::types::LastUpdateStatus, Option<String>)> {
+        lambda::last_update_status(&self.lambda_client, &lambda.name).await
+    }
+
+    pub async fn tags(&self, lambda: &Lambda) -> Result<std::collections::HashMap<String, String>> {
+        lambda::lambda_tags(&self.lambda_client, &lambda.arn).await
+    }
+
+    pub async fn versions(&self, lambda: &Lambda) -> Result<Vec<Version>> {
+        lambda::lambda_versions(&self.lambda_client, &lambda.name).await
+    }
+
+    pub async fn aliases(&self, lambda: &Lambda) -> Result<Vec<Alias>> {
+        lambda::lambda_aliases(&self.lambda_client, &lambda.name).await
+    }
+
+    pub async fn update_alias(&self, lambda: &Lambda, alias: &Alias) -> Result<Alias> {
+        lambda::update_alias(&self.lambda_client, &lambda.name, alias).await
+    }
+
+    // metrics of every version an alias routes to, or of a single version
+    pub async fn version_metrics(
+        &self,
+        lambda: &Lambda,
+        alias: Option<&Alias>,
+        version: Option<&Version>,
+    ) -> Result<Vec<VersionMetrics>> {
+        let targets: Vec<(String, Option<f64>, String, Option<String>)> = match (alias, version) {
+            (Some(alias), _) => alias
+                .traffic_split()
+                .into_iter()
+                .map(|(version, weight)| {
+                    (
+                        version.clone(),
+                        Some(weight),
+                        format!("{}:{}", lambda.name, alias.name),
+                        Some(version),
+                    )
+                })
+                .collect(),
+            (None, Some(version)) => vec![(
+                version.version.clone(),
+                None,
+                format!("{}:{}", lambda.name, version.version),
+                None,
+            )],
+            (None, None) => vec![],
+        };
+
+        let metrics =
+            targets
+                .into_iter()
+                .map(|(version, weight, resource, executed_version)| async move {
+                    let metrics = cloudwatch::version_metrics(
+                        &self.cw_client,
+                        &lambda.name,
+                        &resource,
+                        executed_version.as_deref(),
+                    )
+                    .await?;
+                    Ok(VersionMetrics {
+                        version,
+                        weight,
+                        metrics,
+                    })
+                });
+
+        futures::future::join_all(metrics)
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>>>()
     }
 
     pub async fn event_source_mappings(&self, lambda: &Lambda) -> Result<Vec<EventSourceMapping>> {
@@ -46,10 +315,155 @@ impl AWS {
         let mut event_sources =
             lambda::lambda_event_source_mappings(&self.lambda_client, &lambda.name).await?;
         event_sources.extend(eb_event_source_mappings);
+        event_sources.extend(self.push_event_source_mappings(lambda).await?);
 
         Ok(event_sources)
     }
 
+    // push style triggers are derived from the resource policy, then confirmed against the
+    // source where we have the permissions to do so. A policy that cannot be read or parsed
+    // is treated as having no triggers, the mappings pulled by lambda are still shown
+    async fn push_event_source_mappings(&self, lambda: &Lambda) -> Result<Vec<EventSourceMapping>> {
+        let policy = match lambda::lambda_policy(&self.lambda_client, &lambda.name).await {
+            Ok(Some(policy)) => policy,
+            Ok(None) | Err(_) => return Ok(vec![]),
+        };
+
+        let triggers = match resource_policy::triggers(&policy) {
+            Ok(triggers) => triggers,
+            Err(_) => return Ok(vec![]),
+        };
+
+        let mappings = triggers.into_iter().map(|trigger| async move {
+            match trigger {
+                PolicyTrigger::S3 { bucket } => {
+                    let events =
+                        s3::bucket_notification_events(&self.s3_client, &bucket, &lambda.arn).await;
+                    let (events, state) = match events {
+                        Ok(events) if events.is_empty() => {
+                            (events, EventSourceMappingState::Disabled)
+                        }
+                        Ok(events) => (events, EventSourceMappingState::Enabled),
+                        Err(_) => (vec![], EventSourceMappingState::Enabled),
+                    };
+                    Ok(EventSourceMapping::S3 {
+                        name: bucket,
+                        events,
+                        state,
+                    })
+                }
+                PolicyTrigger::Sns { topic_arn } => {
+                    let state =
+                        match sns::is_subscribed(&self.sns_client, &topic_arn, &lambda.arn).await {
+                            Ok(false) => EventSourceMappingState::Disabled,
+                            _ => EventSourceMappingState::Enabled,
+                        };
+                    Ok(EventSourceMapping::SNS {
+                        name: topic_arn
+                            .rsplit(':')
+                            .next()
+                            .unwrap_or(&topic_arn)
+                            .to_string(),
+                        topic_arn,
+                        state,
+                    })
+                }
+                PolicyTrigger::ApiGateway { source_arn } => {
+                    let resource = source_arn.rsplit(':').next().unwrap_or(&source_arn);
+                    let (api_id, route) = resource.split_once('/').unwrap_or((resource, "*"));
+                    Ok(EventSourceMapping::ApiGateway {
+                        name: api_id.to_string(),
+                        route: route.to_string(),
+                        state: EventSourceMappingState::Enabled,
+                    })
+                }
+                PolicyTrigger::FunctionUrl { auth_type } => {
+                    let url = lambda::lambda_function_url(&self.lambda_client, &lambda.name).await;
+                    let (name, auth_type, state) = match url {
+                        Ok(Some((url, auth_type))) => {
+                            (url, auth_type, EventSourceMappingState::Enabled)
+                        }
+                        Ok(None) => (
+                            "<deleted>".to_string(),
+                            auth_type,
+                            EventSourceMappingState::Disabled,
+                        ),
+                        Err(_) => (
+                            "unknown".to_string(),
+                            auth_type,
+                            EventSourceMappingState::Enabled,
+                        ),
+                    };
+                    Ok(EventSourceMapping::FunctionUrl {
+                        name,
+                        auth_type,
+                        state,
+                    })
+                }
+            }
+        });
+
+        futures::future::join_all(mappings)
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>>>()
+    }
+
+    pub async fn queue_health(
+        &self,
+        event_source_mappings: &[EventSourceMapping],
+    ) -> Vec<QueueHealth> {
+        let queues = event_source_mappings.iter().filter_map(|m| match m {
+            EventSourceMapping::SQS { name, arn, .. } => Some((name, arn)),
+            _ => None,
+        });
+
+        let health = queues.map(|(name, arn)| async move {
+            // one queue failing shouldn't take the dashboard down, it is shown as unavailable
+            let (metrics, metrics_error) =
+                match cloudwatch::queue_metrics(&self.cw_client, name).await {
+                    Ok(metrics) => (metrics, None),
+                    Err(e) => (vec![], Some(e.to_string())),
+                };
+
+            // the redrive policy and dlq depth need sqs permissions, so they are best effort
+            let redrive_policy = sqs::redrive_policy(&self.sqs_client, arn)
+                .await
+                .ok()
+                .flatten();
+
+            let dead_letter_depth = match &redrive_policy {
+                Some(policy) => sqs::queue_depth(&self.sqs_client, &policy.dead_letter_target_arn)
+                    .await
+                    .ok(),
+                None => None,
+            };
+
+            QueueHealth {
+                name: name.clone(),
+                arn: arn.clone(),
+                redrive_policy,
+                dead_letter_depth,
+                metrics,
+                metrics_error,
+            }
+        });
+
+        futures::future::join_all(health).await
+    }
+
+    pub async fn peek_messages(&self, queue_arn: &str) -> Result<Vec<QueueMessage>> {
+        sqs::peek_messages(&self.sqs_client, queue_arn).await
+    }
+
+    pub async fn redrive(&self, dead_letter_arn: &str, source_arn: &str) -> Result<String> {
+        sqs::redrive(&self.sqs_client, dead_letter_arn, source_arn).await
+    }
+
+    pub async fn invoke(&self, lambda: &Lambda, payload: &str) -> Result<InvocationResult> {
+        lambda::invoke_lambda(&self.lambda_client, &lambda.name, payload).await
+    }
+
     pub async fn clear_cache(&self) {
         lambda::clear_cache();
     }
@@ -0,0 +1,240 @@
+use anyhow::Result;
+use serde_json::Value;
+
+/// A push-style trigger derived from a statement in a function's resource policy.
+#[derive(Debug, Clone)]
+pub(crate) enum PolicyTrigger {
+    S3 { bucket: String },
+    Sns { topic_arn: String },
+    ApiGateway { source_arn: String },
+    FunctionUrl { auth_type: String },
+}
+
+// policy values can either be a single item or a list of items
+fn as_list(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Array(values) => values.iter().collect(),
+        Value::Null => vec![],
+        value => vec![value],
+    }
+}
+
+fn service_principals(statement: &Value) -> Vec<String> {
+    match statement.get("Principal") {
+        Some(Value::Object(principal)) => principal
+            .get("Service")
+            .map(|service| {
+                as_list(service)
+                    .iter()
+                    .filter_map(|s| s.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        Some(Value::String(principal)) => vec![principal.clone()],
+        _ => vec![],
+    }
+}
+
+fn actions(statement: &Value) -> Vec<String> {
+    statement
+        .get("Action")
+        .map(|action| {
+            as_list(action)
+                .iter()
+                .filter_map(|a| a.as_str().map(|a| a.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// condition keys are case insensitive, so look them up regardless of the operator used
+fn condition_value(statement: &Value, key: &str) -> Option<String> {
+    let conditions = statement.get("Condition")?.as_object()?;
+
+    conditions.values().find_map(|operator| {
+        operator.as_object()?.iter().find_map(|(k, v)| {
+            if k.eq_ignore_ascii_case(key) {
+                as_list(v)
+                    .first()
+                    .and_then(|v| v.as_str().map(|v| v.to_string()))
+            } else {
+                None
+            }
+        })
+    })
+}
+
+fn trigger_from_statement(statement: &Value) -> Option<PolicyTrigger> {
+    if statement.get("Effect").and_then(|e| e.as_str()) != Some("Allow") {
+        return None;
+    }
+
+    if actions(statement)
+        .iter()
+        .any(|a| a == "lambda:InvokeFunctionUrl")
+    {
+        let auth_type = condition_value(statement, "lambda:FunctionUrlAuthType")
+            .unwrap_or("AWS_IAM".to_string());
+        return Some(PolicyTrigger::FunctionUrl { auth_type });
+    }
+
+    let source_arn = condition_value(statement, "AWS:SourceArn");
+
+    service_principals(statement).iter().find_map(|principal| {
+        match (principal.as_str(), &source_arn) {
+            ("s3.amazonaws.com", Some(arn)) => Some(PolicyTrigger::S3 {
+                bucket: arn.rsplit(":::").next().unwrap_or(arn).to_string(),
+            }),
+            ("sns.amazonaws.com", Some(arn)) => Some(PolicyTrigger::Sns {
+                topic_arn: arn.clone(),
+            }),
+            ("apigateway.amazonaws.com", Some(arn)) => Some(PolicyTrigger::ApiGateway {
+                source_arn: arn.clone(),
+            }),
+            _ => None,
+        }
+    })
+}
+
+pub(crate) fn triggers(policy: &str) -> Result<Vec<PolicyTrigger>> {
+    let policy: Value = serde_json::from_str(policy)?;
+
+    let statements = policy.get("Statement").map(as_list).unwrap_or_default();
+
+    let mut triggers: Vec<PolicyTrigger> = Vec::new();
+
+    for trigger in statements.into_iter().filter_map(trigger_from_statement) {
+        // a function url has a statement per action, only list it once
+        let is_duplicate_url = matches!(trigger, PolicyTrigger::FunctionUrl { .. })
+            && triggers
+                .iter()
+                .any(|t| matches!(t, PolicyTrigger::FunctionUrl { .. }));
+
+        if !is_duplicate_url {
+            triggers.push(trigger);
+        }
+    }
+
+    Ok(triggers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(statements: &str) -> String {
+        format!(
+            r#"{{"Version": "2012-10-17", "Statement": {}}}"#,
+            statements
+        )
+    }
+
+    #[test]
+    fn triggers_reads_the_s3_bucket() {
+        let triggers = triggers(&policy(
+            r#"[{
+                "Effect": "Allow",
+                "Principal": {"Service": "s3.amazonaws.com"},
+                "Action": "lambda:InvokeFunction",
+                "Condition": {"ArnLike": {"AWS:SourceArn": "arn:aws:s3:::uploads"}}
+            }]"#,
+        ))
+        .unwrap();
+
+        assert!(matches!(
+            triggers.as_slice(),
+            [PolicyTrigger::S3 { bucket }] if bucket == "uploads"
+        ));
+    }
+
+    #[test]
+    fn triggers_reads_sns_and_api_gateway_source_arns() {
+        let triggers = triggers(&policy(
+            r#"[{
+                "Effect": "Allow",
+                "Principal": {"Service": "sns.amazonaws.com"},
+                "Action": "lambda:InvokeFunction",
+                "Condition": {"ArnLike": {"aws:sourcearn": "arn:aws:sns:eu-west-1:123456789012:orders"}}
+            }, {
+                "Effect": "Allow",
+                "Principal": {"Service": "apigateway.amazonaws.com"},
+                "Action": "lambda:InvokeFunction",
+                "Condition": {"ArnLike": {"AWS:SourceArn": "arn:aws:execute-api:eu-west-1:123456789012:abc123/*/POST/orders"}}
+            }]"#,
+        ))
+        .unwrap();
+
+        assert!(matches!(
+            triggers.as_slice(),
+            [
+                PolicyTrigger::Sns { topic_arn },
+                PolicyTrigger::ApiGateway { source_arn },
+            ] if topic_arn == "arn:aws:sns:eu-west-1:123456789012:orders"
+                && source_arn == "arn:aws:execute-api:eu-west-1:123456789012:abc123/*/POST/orders"
+        ));
+    }
+
+    #[test]
+    fn triggers_lists_a_function_url_once() {
+        let triggers = triggers(&policy(
+            r#"[{
+                "Effect": "Allow",
+                "Principal": "*",
+                "Action": "lambda:InvokeFunctionUrl",
+                "Condition": {"StringEquals": {"lambda:FunctionUrlAuthType": "NONE"}}
+            }, {
+                "Effect": "Allow",
+                "Principal": "*",
+                "Action": ["lambda:InvokeFunctionUrl"]
+            }]"#,
+        ))
+        .unwrap();
+
+        assert!(matches!(
+            triggers.as_slice(),
+            [PolicyTrigger::FunctionUrl { auth_type }] if auth_type == "NONE"
+        ));
+    }
+
+    #[test]
+    fn triggers_defaults_a_function_url_to_iam_auth() {
+        let triggers = triggers(&policy(
+            r#"{"Effect": "Allow", "Principal": "*", "Action": "lambda:InvokeFunctionUrl"}"#,
+        ))
+        .unwrap();
+
+        assert!(matches!(
+            triggers.as_slice(),
+            [PolicyTrigger::FunctionUrl { auth_type }] if auth_type == "AWS_IAM"
+        ));
+    }
+
+    #[test]
+    fn triggers_ignores_denied_and_unconditioned_statements() {
+        let triggers = triggers(&policy(
+            r#"[{
+                "Effect": "Deny",
+                "Principal": {"Service": "s3.amazonaws.com"},
+                "Action": "lambda:InvokeFunction",
+                "Condition": {"ArnLike": {"AWS:SourceArn": "arn:aws:s3:::uploads"}}
+            }, {
+                "Effect": "Allow",
+                "Principal": {"Service": "sns.amazonaws.com"},
+                "Action": "lambda:InvokeFunction"
+            }, {
+                "Effect": "Allow",
+                "Principal": {"Service": "events.amazonaws.com"},
+                "Action": "lambda:InvokeFunction",
+                "Condition": {"ArnLike": {"AWS:SourceArn": "arn:aws:events:eu-west-1:123456789012:rule/nightly"}}
+            }]"#,
+        ))
+        .unwrap();
+
+        assert!(triggers.is_empty());
+    }
+
+    #[test]
+    fn triggers_fails_on_invalid_json() {
+        assert!(triggers("not a policy").is_err());
+    }
+}
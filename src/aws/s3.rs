@@ -0,0 +1,23 @@
+use anyhow::Result;
+
+use super::lambda::is_lambda_arn;
+
+// get the events of any bucket notifications which target the lambda
+pub async fn bucket_notification_events(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    lambda_arn: &str,
+) -> Result<Vec<String>> {
+    let response = client
+        .get_bucket_notification_configuration()
+        .bucket(bucket)
+        .send()
+        .await?;
+
+    Ok(response
+        .lambda_function_configurations()
+        .iter()
+        .filter(|c| is_lambda_arn(c.lambda_function_arn(), lambda_arn))
+        .flat_map(|c| c.events().iter().map(|e| e.as_str().to_string()))
+        .collect())
+}
@@ -0,0 +1,37 @@
+use anyhow::Result;
+
+use super::lambda::is_lambda_arn;
+
+// check whether the lambda is subscribed to the topic
+pub async fn is_subscribed(
+    client: &aws_sdk_sns::Client,
+    topic_arn: &str,
+    lambda_arn: &str,
+) -> Result<bool> {
+    let mut next_token = None;
+    loop {
+        let response = client
+            .list_subscriptions_by_topic()
+            .topic_arn(topic_arn)
+            .set_next_token(next_token)
+            .send()
+            .await?;
+
+        let subscribed = response.subscriptions().iter().any(|s| {
+            s.protocol() == Some("lambda")
+                && s.endpoint()
+                    .map(|e| is_lambda_arn(e, lambda_arn))
+                    .unwrap_or(false)
+        });
+
+        if subscribed {
+            return Ok(true);
+        }
+
+        next_token = response.next_token().map(|s| s.to_string());
+        if next_token.is_none() {
+            break;
+        }
+    }
+    Ok(false)
+}
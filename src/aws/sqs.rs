@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use aws_sdk_sqs::types::QueueAttributeName;
+
+use crate::core::{QueueMessage, RedrivePolicy};
+
+// arn:aws:sqs:<region>:<account>:<name>
+pub async fn queue_url(client: &aws_sdk_sqs::Client, queue_arn: &str) -> Result<String> {
+    let parts = queue_arn.split(':').collect::<Vec<&str>>();
+
+    let (account, name) = match parts.as_slice() {
+        [.., account, name] => (account.to_string(), name.to_string()),
+        _ => return Err(anyhow!("invalid queue arn: {}", queue_arn)),
+    };
+
+    let response = client
+        .get_queue_url()
+        .queue_name(name)
+        .queue_owner_aws_account_id(account)
+        .send()
+        .await?;
+
+    Ok(response.queue_url().unwrap_or_default().to_string())
+}
+
+async fn queue_attributes(
+    client: &aws_sdk_sqs::Client,
+    queue_arn: &str,
+    attribute_names: Vec<QueueAttributeName>,
+) -> Result<HashMap<QueueAttributeName, String>> {
+    let queue_url = queue_url(client, queue_arn).await?;
+
+    let response = client
+        .get_queue_attributes()
+        .queue_url(queue_url)
+        .set_attribute_names(Some(attribute_names))
+        .send()
+        .await?;
+
+    Ok(response.attributes().cloned().unwrap_or_default())
+}
+
+pub async fn redrive_policy(
+    client: &aws_sdk_sqs::Client,
+    queue_arn: &str,
+) -> Result<Option<RedrivePolicy>> {
+    let attributes =
+        queue_attributes(client, queue_arn, vec![QueueAttributeName::RedrivePolicy]).await?;
+
+    let policy = match attributes.get(&QueueAttributeName::RedrivePolicy) {
+        Some(policy) => serde_json::from_str::<serde_json::Value>(policy)?,
+        None => return Ok(None),
+    };
+
+    let dead_letter_target_arn = policy
+        .get("deadLetterTargetArn")
+        .and_then(|arn| arn.as_str())
+        .map(|arn| arn.to_string());
+
+    // the max receive count can either be a number or a string
+    let max_receive_count = policy
+        .get("maxReceiveCount")
+        .and_then(|count| {
+            count
+                .as_i64()
+                .or_else(|| count.as_str().and_then(|c| c.parse().ok()))
+        })
+        .unwrap_or(0);
+
+    Ok(
+        dead_letter_target_arn.map(|dead_letter_target_arn| RedrivePolicy {
+            dead_letter_target_arn,
+            max_receive_count,
+        }),
+    )
+}
+
+pub async fn queue_depth(client: &aws_sdk_sqs::Client, queue_arn: &str) -> Result<i64> {
+    let attributes = queue_attributes(
+        client,
+        queue_arn,
+        vec![QueueAttributeName::ApproximateNumberOfMessages],
+    )
+    .await?;
+
+    Ok(attributes
+        .get(&QueueAttributeName::ApproximateNumberOfMessages)
+        .and_then(|depth| depth.parse().ok())
+        .unwrap_or(0))
+}
+
+// receive messages with a zero visibility timeout, so peeking doesn't hide them from consumers
+pub async fn peek_messages(
+    client: &aws_sdk_sqs::Client,
+    queue_arn: &str,
+) -> Result<Vec<QueueMessage>> {
+    let queue_url = queue_url(client, queue_arn).await?;
+
+    let response = client
+        .receive_message()
+        .queue_url(queue_url)
+        .max_number_of_messages(10)
+        .visibility_timeout(0)
+        .attribute_names(QueueAttributeName::All)
+        .message_attribute_names("All")
+        .send()
+        .await?;
+
+    Ok(response
+        .messages()
+        .iter()
+        .map(|message| {
+            let mut attributes = message
+                .attributes()
+                .map(|attributes| {
+                    attributes
+                        .iter()
+                        .map(|(k, v)| (k.as_str().to_string(), v.clone()))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            attributes.sort();
+
+            let mut message_attributes = message
+                .message_attributes()
+                .map(|attributes| {
+                    attributes
+                        .iter()
+                        .map(|(k, v)| {
+                            (
+                                k.clone(),
+                                v.string_value()
+                                    .map(|v| v.to_string())
+                                    .unwrap_or(format!("<{}>", v.data_type())),
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            message_attributes.sort();
+
+            QueueMessage {
+                message_id: message.message_id().unwrap_or_default().to_string(),
+                body: message.body().unwrap_or_default().to_string(),
+                attributes,
+                message_attributes,
+            }
+        })
+        .collect())
+}
+
+// move the messages in a dead-letter queue back to the queue they came from
+pub async fn redrive(
+    client: &aws_sdk_sqs::Client,
+    dead_letter_arn: &str,
+    source_arn: &str,
+) -> Result<String> {
+    let response = client
+        .start_message_move_task()
+        .source_arn(dead_letter_arn)
+        .destination_arn(source_arn)
+        .send()
+        .await?;
+
+    Ok(response.task_handle().unwrap_or_default().to_string())
+}
@@ -0,0 +1,404 @@
+use std::{collections::BTreeMap, fs, io::Write, path::Path};
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::core::Lambda;
+
+/// Settings read from `~/.config/shepherd/config.json`, anything missing falls back to its
+/// default.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub drift: DriftConfig,
+    pub metrics: MetricsConfig,
+    pub search: SearchConfig,
+}
+
+/// How functions are grouped into stages of the same logical function.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct DriftConfig {
+    /// a name pattern where `{stage}` marks the stage, other `{placeholders}` match anything
+    pub pattern: String,
+    /// the values `{stage}` can take in the name pattern, anything if empty
+    pub stages: Vec<String>,
+    /// group by the value of this tag instead of the name pattern
+    pub stage_tag: Option<String>,
+}
+
+impl Default for DriftConfig {
+    fn default() -> Self {
+        Self {
+            pattern: "{service}-{stage}-{name}".to_string(),
+            stages: [
+                "dev",
+                "test",
+                "qa",
+                "uat",
+                "staging",
+                "preprod",
+                "prod",
+                "production",
+            ]
+            .iter()
+            .map(|stage| stage.to_string())
+            .collect(),
+            stage_tag: None,
+        }
+    }
+}
+
+/// An attribute of a function shown as a column of the search results.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Column {
+    /// always shown, it can only be sorted by
+    Name,
+    Runtime,
+    Memory,
+    Timeout,
+    Architecture,
+    LastModified,
+    CodeSize,
+    Description,
+    Tags,
+}
+
+impl Column {
+    /// The columns that can be shown, in the order they are offered.
+    pub const OPTIONAL: [Column; 8] = [
+        Column::Runtime,
+        Column::Memory,
+        Column::Timeout,
+        Column::Architecture,
+        Column::LastModified,
+        Column::CodeSize,
+        Column::Description,
+        Column::Tags,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Column::Name => "name",
+            Column::Runtime => "runtime",
+            Column::Memory => "memory",
+            Column::Timeout => "timeout",
+            Column::Architecture => "arch",
+            Column::LastModified => "last modified",
+            Column::CodeSize => "code size",
+            Column::Description => "description",
+            Column::Tags => "tags",
+        }
+    }
+
+    pub fn value(&self, lambda: &Lambda) -> String {
+        match self {
+            Column::Name => lambda.name.clone(),
+            Column::Runtime => lambda.runtime.clone(),
+            Column::Memory => lambda.memory.to_string(),
+            Column::Timeout => lambda.timeout.to_string(),
+            Column::Architecture => lambda.architecture.clone(),
+            // e.g. 2024-01-31T12:00:00.000+0000, the minutes are enough
+            Column::LastModified => lambda
+                .last_modified
+                .chars()
+                .take(16)
+                .collect::<String>()
+                .replace('T', " "),
+            Column::CodeSize => match lambda.code_size {
+                size if size >= 1024 * 1024 => format!("{:.1} MB", size as f64 / 1048576.0),
+                size => format!("{:.1} KB", size as f64 / 1024.0),
+            },
+            Column::Description => lambda.description.clone(),
+            Column::Tags => {
+                let mut tags = lambda
+                    .tags
+                    .iter()
+                    .map(|(k, v)| format!("{}={}", k, v))
+                    .collect::<Vec<_>>();
+                tags.sort();
+                tags.join(", ")
+            }
+        }
+    }
+
+    pub fn compare(&self, a: &Lambda, b: &Lambda) -> std::cmp::Ordering {
+        match self {
+            Column::Memory => a.memory.cmp(&b.memory),
+            Column::Timeout => a.timeout.cmp(&b.timeout),
+            Column::CodeSize => a.code_size.cmp(&b.code_size),
+            // the timestamps sort as text
+            column => column.value(a).cmp(&column.value(b)),
+        }
+    }
+}
+
+/// The tag CloudFormation puts on every resource of a stack.
+const STACK_TAG: &str = "aws:cloudformation:stack-name";
+/// The tag on functions deployed from the Serverless Application Repository.
+const APPLICATION_TAG: &str = "serverlessrepo:applicationId";
+
+/// How the search results are grouped, changed from the search page.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupBy {
+    /// the CloudFormation stack the function was deployed with
+    Stack,
+    /// the serverless application the function belongs to
+    Application,
+    /// the value of the tag in `search.group_tag`
+    Tag,
+}
+
+impl GroupBy {
+    /// The next grouping, or none after the last one, skipping tags while no tag is set.
+    pub fn next(current: Option<GroupBy>, tag: Option<&str>) -> Option<GroupBy> {
+        match current {
+            None => Some(GroupBy::Stack),
+            Some(GroupBy::Stack) => Some(GroupBy::Application),
+            Some(GroupBy::Application) => tag.map(|_| GroupBy::Tag),
+            Some(GroupBy::Tag) => None,
+        }
+    }
+
+    pub fn title(&self, tag: Option<&str>) -> String {
+        match self {
+            GroupBy::Stack => "stack".to_string(),
+            GroupBy::Application => "application".to_string(),
+            GroupBy::Tag => format!("tag {}", tag.unwrap_or_default()),
+        }
+    }
+
+    /// The group of a function, none if it has nothing to be grouped by.
+    pub fn group(&self, lambda: &Lambda, tag: Option<&str>) -> Option<String> {
+        match self {
+            GroupBy::Stack => lambda.tags.get(STACK_TAG).cloned(),
+            // applications from the repository are named by the end of their arn, those
+            // deployed with SAM are a stack of their own
+            GroupBy::Application => match lambda.tags.get(APPLICATION_TAG) {
+                Some(id) => Some(id.rsplit('/').next().unwrap_or(id).to_string()),
+                None if lambda.tags.get("lambda:createdBy").map(String::as_str) == Some("SAM") => {
+                    lambda.tags.get(STACK_TAG).cloned()
+                }
+                None => None,
+            },
+            GroupBy::Tag => tag.and_then(|tag| lambda.tags.get(tag)).cloned(),
+        }
+    }
+}
+
+/// The order of the search results when not ranked by how well they match.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SortOrder {
+    pub column: Column,
+    pub descending: bool,
+}
+
+/// The columns of the search results, changed from the search page.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct SearchConfig {
+    pub columns: Vec<Column>,
+    pub sort: Option<SortOrder>,
+    /// whether the preview of the highlighted function is shown next to the results
+    pub preview: bool,
+    pub group_by: Option<GroupBy>,
+    /// the tag key to group by with `GroupBy::Tag`
+    pub group_tag: Option<String>,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            columns: vec![Column::Runtime, Column::Memory],
+            sort: None,
+            preview: false,
+            group_by: None,
+            group_tag: None,
+        }
+    }
+}
+
+/// Extra charts on the dashboard.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct MetricsConfig {
+    pub expressions: Vec<MetricExpression>,
+    pub custom: Vec<CustomMetric>,
+}
+
+/// A CloudWatch metric math expression over the dashboard's metrics, which are referenced by
+/// their ids: `invocations`, `errors`, `duration`, `concurrent_executions` and `throttles`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MetricExpression {
+    /// must start with a lowercase letter and be unique among the dashboard's metrics
+    pub id: String,
+    pub expression: String,
+    pub label: Option<String>,
+}
+
+/// A metric published by the function itself, such as a business metric emitted through EMF.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CustomMetric {
+    /// the function names the metric is charted for, `*` matches any characters
+    #[serde(default = "any_function")]
+    pub functions: String,
+    /// lets expressions reference the metric, generated when not set
+    pub id: Option<String>,
+    pub namespace: String,
+    pub metric_name: String,
+    /// dimension values can contain a `{function}` placeholder for the function name
+    #[serde(default)]
+    pub dimensions: BTreeMap<String, String>,
+    #[serde(default = "sum")]
+    pub stat: String,
+    pub label: Option<String>,
+}
+
+fn any_function() -> String {
+    "*".to_string()
+}
+
+fn sum() -> String {
+    "Sum".to_string()
+}
+
+fn matches_glob(pattern: &str, name: &str) -> bool {
+    let parts = pattern.split('*').collect::<Vec<_>>();
+
+    if parts.len() == 1 {
+        return pattern == name;
+    }
+
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !name.starts_with(first) || !name[first.len()..].ends_with(last) {
+        return false;
+    }
+
+    // the parts in between have to appear in order, between the first and last part
+    let mut rest = &name[first.len()..name.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+
+    true
+}
+
+impl CustomMetric {
+    pub fn applies_to(&self, function_name: &str) -> bool {
+        matches_glob(&self.functions, function_name)
+    }
+
+    pub fn dimensions_for(&self, function_name: &str) -> Vec<(String, String)> {
+        self.dimensions
+            .iter()
+            .map(|(name, value)| (name.clone(), value.replace("{function}", function_name)))
+            .collect()
+    }
+}
+
+// the configuration file with its search settings replaced, the rest of it is kept as it is,
+// including settings this version doesn't know about
+fn with_search(content: Option<&str>, search: &SearchConfig) -> Result<String> {
+    let mut config = match content {
+        Some(content) => serde_json::from_str(content)
+            .map_err(|e| anyhow!("the configuration is not valid JSON: {}", e))?,
+        None => serde_json::json!({}),
+    };
+
+    match config.as_object_mut() {
+        Some(settings) => settings.insert("search".to_string(), serde_json::to_value(search)?),
+        None => bail!("the configuration is not a JSON object"),
+    };
+
+    Ok(serde_json::to_string_pretty(&config)?)
+}
+
+fn config_path() -> String {
+    format!(
+        "{}/.config/shepherd/config.json",
+        dirs::home_dir().unwrap().to_str().unwrap()
+    )
+}
+
+impl Config {
+    pub fn load() -> Self {
+        let path = config_path();
+
+        if !Path::new(&path).exists() {
+            return Self::default();
+        }
+
+        fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the search settings into the file, leaving everything else in it as it is. A file
+    /// that can't be read is not saved over, so the settings in it are not lost.
+    pub fn save_search(search: &SearchConfig) -> Result<()> {
+        let path = config_path();
+
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => Some(content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => bail!("failed to read {}: {}", path, e),
+        };
+        let content = with_search(content.as_deref(), search)
+            .map_err(|e| anyhow!("{}, not saving over {}", e, path))?;
+
+        if let Some(parent) = Path::new(&path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = fs::File::create(&path)?;
+        file.write_all(content.as_bytes())?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search() -> SearchConfig {
+        SearchConfig {
+            preview: true,
+            ..SearchConfig::default()
+        }
+    }
+
+    #[test]
+    fn with_search_keeps_the_other_settings() {
+        let content = with_search(
+            Some(
+                r#"{"drift": {"stage_tag": "stage"}, "unknown": 1, "search": {"preview": false}}"#,
+            ),
+            &search(),
+        )
+        .unwrap();
+        let config: serde_json::Value = serde_json::from_str(&content).unwrap();
+
+        assert_eq!(config["drift"]["stage_tag"], "stage");
+        assert_eq!(config["unknown"], 1);
+        assert_eq!(config["search"]["preview"], true);
+    }
+
+    #[test]
+    fn with_search_creates_a_missing_file() {
+        let config: Config = serde_json::from_str(&with_search(None, &search()).unwrap()).unwrap();
+
+        assert_eq!(config.search, search());
+    }
+
+    #[test]
+    fn with_search_refuses_a_file_it_cant_read() {
+        assert!(with_search(Some("{\"drift\": "), &search()).is_err());
+        assert!(with_search(Some("[]"), &search()).is_err());
+    }
+}
@@ -1,10 +1,31 @@
-#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
 pub(crate) struct Lambda {
     pub name: String,
     pub arn: String,
     pub runtime: String,
     pub memory: i64,
     pub timeout: i64,
+    // the fields below were added after the cache was introduced, older caches leave them empty
+    #[serde(default)]
+    pub architecture: String,
+    #[serde(default)]
+    pub layers: Vec<String>,
+    #[serde(default)]
+    pub tags: std::collections::HashMap<String, String>,
+    /// the services of the event sources mapped to the function, e.g. `sqs`
+    #[serde(default)]
+    pub triggers: Vec<String>,
+    #[serde(default)]
+    pub last_modified: String,
+    #[serde(default)]
+    pub code_size: i64,
+    #[serde(default)]
+    pub description: String,
+    /// the names of the queues, tables and streams mapped to the function
+    #[serde(default)]
+    pub trigger_names: Vec<String>,
+    #[serde(default)]
+    pub environment_keys: Vec<String>,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
@@ -17,6 +38,7 @@ pub enum EventSourceMappingState {
 pub(crate) enum EventSourceMapping {
     SQS {
         name: String,
+        arn: String,
         batch_size: i64,
         state: EventSourceMappingState,
         batch_window: i64,
@@ -26,6 +48,26 @@ pub(crate) enum EventSourceMapping {
         event_bus_name: String,
         state: EventSourceMappingState,
     },
+    S3 {
+        name: String,
+        events: Vec<String>,
+        state: EventSourceMappingState,
+    },
+    SNS {
+        name: String,
+        topic_arn: String,
+        state: EventSourceMappingState,
+    },
+    ApiGateway {
+        name: String,
+        route: String,
+        state: EventSourceMappingState,
+    },
+    FunctionUrl {
+        name: String,
+        auth_type: String,
+        state: EventSourceMappingState,
+    },
 }
 
 impl EventSourceMapping {
@@ -33,6 +75,10 @@ impl EventSourceMapping {
         match self {
             Self::SQS { name, .. } => name.clone(),
             Self::EventBridge { name, .. } => name.clone(),
+            Self::S3 { name, .. } => name.clone(),
+            Self::SNS { name, .. } => name.clone(),
+            Self::ApiGateway { name, route, .. } => format!("{} {}", name, route),
+            Self::FunctionUrl { name, .. } => name.clone(),
         }
     }
 
@@ -42,6 +88,11 @@ impl EventSourceMapping {
             Self::EventBridge { event_bus_name, .. } => {
                 format!("EventBridge ({})", event_bus_name).to_string()
             }
+            Self::S3 { events, .. } if events.is_empty() => "S3".to_string(),
+            Self::S3 { events, .. } => format!("S3 ({})", events.join(", ")),
+            Self::SNS { .. } => "SNS".to_string(),
+            Self::ApiGateway { .. } => "API Gateway".to_string(),
+            Self::FunctionUrl { auth_type, .. } => format!("Function URL ({})", auth_type),
         }
     }
 
@@ -56,7 +107,10 @@ impl EventSourceMapping {
         match self {
             Self::SQS { state, .. } => state.clone(),
             Self::EventBridge { state, .. } => state.clone(),
-            _ => EventSourceMappingState::Disabled,
+            Self::S3 { state, .. } => state.clone(),
+            Self::SNS { state, .. } => state.clone(),
+            Self::ApiGateway { state, .. } => state.clone(),
+            Self::FunctionUrl { state, .. } => state.clone(),
         }
     }
 
@@ -79,8 +133,339 @@ pub(crate) struct Metric {
     pub values: Vec<f64>,
 }
 
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+pub(crate) struct AsyncConfig {
+    pub maximum_retry_attempts: Option<i64>,
+    pub maximum_event_age_in_seconds: Option<i64>,
+    pub on_success: Option<String>,
+    pub on_failure: Option<String>,
+    pub dead_letter_target: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub(crate) struct RedrivePolicy {
+    pub dead_letter_target_arn: String,
+    pub max_receive_count: i64,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub(crate) struct QueueHealth {
+    pub name: String,
+    pub arn: String,
+    pub redrive_policy: Option<RedrivePolicy>,
+    pub dead_letter_depth: Option<i64>,
+    pub metrics: Vec<Metric>,
+    /// why the queue metrics couldn't be fetched
+    pub metrics_error: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub(crate) struct DeadLetterQueue {
+    pub arn: String,
+    /// the queue the messages were dead-lettered from, if it can be redriven
+    pub source_arn: Option<String>,
+}
+
+impl DeadLetterQueue {
+    pub fn name(&self) -> String {
+        self.arn.rsplit(':').next().unwrap_or_default().to_string()
+    }
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub(crate) struct QueueMessage {
+    pub message_id: String,
+    pub body: String,
+    pub attributes: Vec<(String, String)>,
+    pub message_attributes: Vec<(String, String)>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub(crate) struct InvocationResult {
+    pub status_code: i64,
+    pub function_error: Option<String>,
+    pub payload: String,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub(crate) struct ProvisionedConcurrency {
+    pub qualifier: String,
+    pub requested: i64,
+    pub allocated: i64,
+    pub available: i64,
+    pub status: String,
+    pub status_reason: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+pub(crate) struct Concurrency {
+    pub reserved: Option<i64>,
+    pub provisioned: Vec<ProvisionedConcurrency>,
+    pub account_limit: i64,
+    pub unreserved: i64,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub(crate) struct Version {
+    pub version: String,
+    pub description: String,
+    pub code_sha256: String,
+    pub last_modified: String,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub(crate) struct Alias {
+    pub name: String,
+    pub function_version: String,
+    pub description: String,
+    pub additional_version_weights: Vec<(String, f64)>,
+    /// the version the alias pointed at before traffic started shifting, kept locally rather
+    /// than on the alias in AWS
+    #[serde(default)]
+    pub previous_version: Option<String>,
+}
+
+impl Alias {
+    /// the share of traffic each version receives, the primary version gets whatever is left
+    pub fn traffic_split(&self) -> Vec<(String, f64)> {
+        let additional: f64 = self.additional_version_weights.iter().map(|(_, w)| w).sum();
+        let mut split = vec![(self.function_version.clone(), 1.0 - additional)];
+        split.extend(self.additional_version_weights.iter().cloned());
+        split
+    }
+
+    pub fn previous_version(&self) -> Option<&str> {
+        self.previous_version.as_deref()
+    }
+
+    pub fn with_previous_version(&self, version: Option<&str>) -> Self {
+        Self {
+            previous_version: version.map(|version| version.to_string()),
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub(crate) struct VersionMetrics {
+    pub version: String,
+    pub weight: Option<f64>,
+    pub metrics: Vec<Metric>,
+}
+
+/// A previous window the current metrics are drawn against.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Baseline {
+    DayAgo,
+    WeekAgo,
+}
+
+impl Baseline {
+    pub fn offset(&self) -> std::time::Duration {
+        match self {
+            Baseline::DayAgo => std::time::Duration::from_secs(86400),
+            Baseline::WeekAgo => std::time::Duration::from_secs(7 * 86400),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Baseline::DayAgo => "yesterday",
+            Baseline::WeekAgo => "last week",
+        }
+    }
+}
+
+/// How far back the fleet overview looks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FleetWindow {
+    Hour,
+    Day,
+    Week,
+}
+
+impl FleetWindow {
+    pub fn seconds(&self) -> u64 {
+        match self {
+            FleetWindow::Hour => 3600,
+            FleetWindow::Day => 86400,
+            FleetWindow::Week => 7 * 86400,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FleetWindow::Hour => "1h",
+            FleetWindow::Day => "24h",
+            FleetWindow::Week => "7d",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            FleetWindow::Hour => FleetWindow::Day,
+            FleetWindow::Day => FleetWindow::Week,
+            FleetWindow::Week => FleetWindow::Hour,
+        }
+    }
+}
+
+/// The totals of a function over the fleet window.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub(crate) struct FleetMetrics {
+    pub lambda: Lambda,
+    pub invocations: f64,
+    pub errors: f64,
+    pub throttles: f64,
+    pub p99_duration: f64,
+}
+
+impl FleetMetrics {
+    pub fn error_rate(&self) -> f64 {
+        if self.invocations == 0.0 {
+            0.0
+        } else {
+            self.errors / self.invocations * 100.0
+        }
+    }
+}
+
+/// The health shown next to a function in the search results.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Health {
+    /// no invocations in the window
+    Idle,
+    Healthy,
+    /// some invocations failed
+    Degraded,
+    /// at least `FAILING_ERROR_RATE` percent of invocations failed
+    Failing,
+}
+
+const FAILING_ERROR_RATE: f64 = 5.0;
+
+/// The recent invocations of a function, oldest first, and its errors over the same window.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub struct RecentActivity {
+    pub invocations: Vec<f64>,
+    pub errors: f64,
+}
+
+impl RecentActivity {
+    pub fn health(&self) -> Health {
+        let invocations: f64 = self.invocations.iter().sum();
+        if invocations == 0.0 {
+            if self.errors > 0.0 {
+                Health::Failing
+            } else {
+                Health::Idle
+            }
+        } else if self.errors / invocations * 100.0 >= FAILING_ERROR_RATE {
+            Health::Failing
+        } else if self.errors > 0.0 {
+            Health::Degraded
+        } else {
+            Health::Healthy
+        }
+    }
+}
+
+/// The configuration of a function, memory, timeout, ephemeral storage and the environment can be
+/// changed with UpdateFunctionConfiguration.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default, PartialEq)]
+pub(crate) struct FunctionConfiguration {
+    pub memory: i64,
+    pub timeout: i64,
+    pub ephemeral_storage: i64,
+    pub environment: Vec<(String, String)>,
+    pub runtime: String,
+    pub role: String,
+    pub layers: Vec<String>,
+    pub subnet_ids: Vec<String>,
+    pub security_group_ids: Vec<String>,
+}
+
+/// A function, or one of its versions or aliases.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub(crate) struct QualifiedLambda {
+    pub lambda: Lambda,
+    pub qualifier: Option<String>,
+}
+
+impl QualifiedLambda {
+    pub fn name(&self) -> String {
+        match &self.qualifier {
+            Some(qualifier) => format!("{}:{}", self.lambda.name, qualifier),
+            None => self.lambda.name.clone(),
+        }
+    }
+}
+
+/// Everything compared when diffing the configuration of two functions.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub(crate) struct FunctionSnapshot {
+    pub function: QualifiedLambda,
+    pub config: FunctionConfiguration,
+    pub reserved_concurrency: Option<i64>,
+    pub provisioned_concurrency: Option<i64>,
+    pub event_source_mappings: Vec<EventSourceMapping>,
+}
+
+/// A setting of a logical function, with its value on each stage.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub(crate) struct DriftSetting {
+    pub name: String,
+    pub values: Vec<String>,
+}
+
+impl DriftSetting {
+    pub fn differs(&self) -> bool {
+        self.values.iter().any(|v| Some(v) != self.values.first())
+    }
+}
+
+/// The functions that make up one logical function deployed to several stages.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub(crate) struct DriftGroup {
+    pub name: String,
+    pub stages: Vec<String>,
+    pub functions: Vec<String>,
+    pub settings: Vec<DriftSetting>,
+    /// the functions that couldn't be read and why, they are left out of the comparison
+    pub errors: Vec<String>,
+}
+
+impl DriftGroup {
+    pub fn drifts(&self) -> bool {
+        self.settings.iter().any(|s| s.differs())
+    }
+
+    /// drifting, or not known to be in line because some functions couldn't be read
+    pub fn needs_attention(&self) -> bool {
+        self.drifts() || !self.errors.is_empty()
+    }
+}
+
+/// What the search page shows of the highlighted function without opening its dashboard.
+#[derive(Debug, Clone)]
+pub(crate) struct Preview {
+    pub lambda: Lambda,
+    pub event_source_mappings: Vec<EventSourceMapping>,
+    /// invocations and errors over the past hour
+    pub metrics: Vec<Metric>,
+    /// why the preview could not be fetched, the function is then shown without it
+    pub error: Option<String>,
+}
+
 pub struct SearchState {
-    pub lambdas: Vec<Lambda>,
+    /// shared with the search page, which only rebuilds its matches when the list changes
+    pub lambdas: std::sync::Arc<Vec<Lambda>>,
+    pub config: crate::config::SearchConfig,
+    /// the recent activity of the functions fetched so far, by name
+    pub activity: std::collections::HashMap<String, RecentActivity>,
+    /// the latest preview fetched, which may be of a function no longer highlighted
+    pub preview: Option<Preview>,
+    pub favourites: crate::favourites::Favourites,
 }
 
 pub struct SearchingState {
@@ -91,6 +476,68 @@ pub struct DashboardState {
     pub lambda: Lambda,
     pub metrics: Vec<Metric>,
     pub event_source_mappings: Vec<EventSourceMapping>,
+    pub async_config: AsyncConfig,
+    pub async_metrics: Vec<Metric>,
+    pub async_error: Option<String>,
+    pub queues: Vec<QueueHealth>,
+    pub concurrency: Concurrency,
+    pub concurrency_error: Option<String>,
+    pub versions: Vec<Version>,
+    pub aliases: Vec<Alias>,
+    pub versions_error: Option<String>,
+    pub baseline: Option<Baseline>,
+    pub baseline_metrics: Vec<Metric>,
+    pub status: Option<String>,
+    pub starred: bool,
+}
+
+pub struct DeadLetterState {
+    pub lambda: Lambda,
+    pub queues: Vec<DeadLetterQueue>,
+    pub selected: usize,
+    pub messages: Vec<QueueMessage>,
+    pub status: Option<String>,
+}
+
+pub struct VersionState {
+    pub lambda: Lambda,
+    pub alias: Option<Alias>,
+    pub version: Option<Version>,
+    pub versions: Vec<Version>,
+    pub version_metrics: Vec<VersionMetrics>,
+    pub status: Option<String>,
+}
+
+pub struct ConfigState {
+    pub lambda: Lambda,
+    pub config: FunctionConfiguration,
+    /// set while an update is being applied
+    pub updating: bool,
+    pub status: Option<String>,
+}
+
+pub struct ConfigDiffState {
+    pub left: FunctionSnapshot,
+    pub right: FunctionSnapshot,
+}
+
+pub struct DriftState {
+    pub groups: Vec<DriftGroup>,
+    /// the name pattern or tag the functions were grouped by
+    pub grouped_by: String,
+    pub status: Option<String>,
+}
+
+pub struct CompareState {
+    pub left: Lambda,
+    pub right: Lambda,
+    /// the same metric of each function, over the same window
+    pub metrics: Vec<(Metric, Metric)>,
+}
+
+pub struct FleetState {
+    pub window: FleetWindow,
+    pub functions: Vec<FleetMetrics>,
 }
 
 pub struct ErrorState {
@@ -102,6 +549,13 @@ pub enum State {
     Search(SearchState),
     Searching(SearchingState),
     Dashboard(DashboardState),
+    DeadLetter(DeadLetterState),
+    Version(VersionState),
+    Config(ConfigState),
+    ConfigDiff(Box<ConfigDiffState>),
+    Drift(DriftState),
+    Compare(CompareState),
+    Fleet(FleetState),
     Error(ErrorState),
     Quit,
 }
@@ -109,5 +563,84 @@ pub enum State {
 pub enum Action {
     Quit,
     Search,
-    PerformSearch { lambda: Lambda },
+    PerformSearch {
+        lambda: Lambda,
+    },
+    ShowBaseline {
+        lambda: Lambda,
+        baseline: Option<Baseline>,
+    },
+    SetReservedConcurrency {
+        lambda: Lambda,
+        reserved: Option<i64>,
+    },
+    SetProvisionedConcurrency {
+        lambda: Lambda,
+        qualifier: String,
+        provisioned: i64,
+    },
+    ShowVersion {
+        lambda: Lambda,
+        alias: Option<Alias>,
+        version: Option<Version>,
+        versions: Vec<Version>,
+    },
+    UpdateAlias {
+        lambda: Lambda,
+        alias: Alias,
+        versions: Vec<Version>,
+    },
+    EditConfiguration {
+        lambda: Lambda,
+    },
+    UpdateConfiguration {
+        lambda: Lambda,
+        config: FunctionConfiguration,
+    },
+    CompareConfiguration {
+        left: QualifiedLambda,
+        right: QualifiedLambda,
+        /// the concurrency and triggers already loaded, when both are the same function
+        loaded: Option<(Concurrency, Vec<EventSourceMapping>)>,
+    },
+    CompareMetrics {
+        left: Lambda,
+        right: Lambda,
+    },
+    DriftReport,
+    Fleet {
+        window: FleetWindow,
+    },
+    FetchRecentActivity {
+        lambdas: Vec<Lambda>,
+    },
+    Preview {
+        lambda: Lambda,
+    },
+    ToggleFavourite {
+        lambda: Lambda,
+    },
+    SaveSearchConfig {
+        config: crate::config::SearchConfig,
+    },
+    ExportDriftReport {
+        groups: Vec<DriftGroup>,
+        grouped_by: String,
+    },
+    PeekDeadLetterQueue {
+        lambda: Lambda,
+        queues: Vec<DeadLetterQueue>,
+        selected: usize,
+    },
+    RedriveDeadLetterQueue {
+        lambda: Lambda,
+        queues: Vec<DeadLetterQueue>,
+        selected: usize,
+    },
+    InvokeWithMessage {
+        lambda: Lambda,
+        queues: Vec<DeadLetterQueue>,
+        selected: usize,
+        payload: String,
+    },
 }
@@ -0,0 +1,139 @@
+use std::{fs, io::Write, path::Path};
+
+use anyhow::Result;
+
+/// How many recently viewed functions are kept.
+const RECENT_LENGTH: usize = 10;
+
+/// The functions starred and the dashboards opened recently, by name, kept for each account
+/// since the same name can be a different function elsewhere.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Favourites {
+    #[serde(skip)]
+    pub account: String,
+    pub starred: Vec<String>,
+    /// most recent first
+    pub recent: Vec<String>,
+}
+
+/// The account of a function, from its arn, e.g. arn:aws:lambda:<region>:<account>:function:<name>.
+pub(crate) fn account(arn: &str) -> Option<&str> {
+    arn.split(':').nth(4).filter(|account| !account.is_empty())
+}
+
+fn favourites_path(account: &str) -> String {
+    format!(
+        "{}/.config/shepherd/favourites-{}.json",
+        dirs::home_dir().unwrap().to_str().unwrap(),
+        account
+    )
+}
+
+impl Favourites {
+    pub fn load(account: &str) -> Self {
+        let favourites: Self = fs::read_to_string(favourites_path(account))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        Self {
+            account: account.to_string(),
+            ..favourites
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = favourites_path(&self.account);
+
+        if let Some(parent) = Path::new(&path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = fs::File::create(&path)?;
+        file.write_all(serde_json::to_string_pretty(self)?.as_bytes())?;
+
+        Ok(())
+    }
+
+    pub fn is_starred(&self, name: &str) -> bool {
+        self.starred.iter().any(|starred| starred == name)
+    }
+
+    /// Star the function, or unstar it if it already was.
+    pub fn toggle(&mut self, name: &str) {
+        if self.is_starred(name) {
+            self.starred.retain(|starred| starred != name);
+        } else {
+            self.starred.push(name.to_string());
+        }
+    }
+
+    /// Put the function at the front of the recently viewed, dropping any earlier view of it.
+    pub fn viewed(&mut self, name: &str) {
+        self.recent.retain(|recent| recent != name);
+        self.recent.insert(0, name.to_string());
+        self.recent.truncate(RECENT_LENGTH);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn account_is_read_from_the_arn() {
+        assert_eq!(
+            account("arn:aws:lambda:eu-west-1:123456789012:function:orders"),
+            Some("123456789012")
+        );
+        assert_eq!(
+            account("arn:aws:lambda:eu-west-1:123456789012:function:orders:live"),
+            Some("123456789012")
+        );
+    }
+
+    #[test]
+    fn account_is_missing_from_a_malformed_arn() {
+        assert_eq!(account(""), None);
+        assert_eq!(account("orders"), None);
+        assert_eq!(account("arn:aws:lambda:eu-west-1::function:orders"), None);
+    }
+
+    #[test]
+    fn toggle_stars_and_unstars() {
+        let mut favourites = Favourites::default();
+
+        favourites.toggle("orders");
+        favourites.toggle("payments");
+        assert!(favourites.is_starred("orders"));
+        assert_eq!(favourites.starred, vec!["orders", "payments"]);
+
+        favourites.toggle("orders");
+        assert!(!favourites.is_starred("orders"));
+        assert_eq!(favourites.starred, vec!["payments"]);
+    }
+
+    #[test]
+    fn viewed_keeps_the_most_recent_first() {
+        let mut favourites = Favourites::default();
+
+        for i in 0..12 {
+            favourites.viewed(&format!("function-{}", i));
+        }
+        favourites.viewed("function-5");
+
+        assert_eq!(favourites.recent.len(), RECENT_LENGTH);
+        assert_eq!(favourites.recent[0], "function-5");
+        assert_eq!(favourites.recent[1], "function-11");
+        assert_eq!(
+            favourites
+                .recent
+                .iter()
+                .filter(|r| *r == "function-5")
+                .count(),
+            1
+        );
+        assert!(!favourites.recent.contains(&"function-1".to_string()));
+    }
+}
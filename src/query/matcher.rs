@@ -0,0 +1,357 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc, Arc, Condvar, Mutex,
+    },
+    time::Duration,
+};
+
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+
+use super::{Field, Query};
+use crate::{config::SortOrder, core::Lambda};
+
+/// How long a search waits for its results before the previous ones are drawn, slower searches
+/// show up on a later frame.
+const FRAME_BUDGET: Duration = Duration::from_millis(15);
+
+/// How many functions are scored between checks for a newer query.
+const CANCEL_INTERVAL: usize = 256;
+
+/// A function matching the query, by its position in the list, with the field and value that
+/// matched and the positions of the matched characters in the value.
+#[derive(Debug, Clone)]
+pub(crate) struct Match {
+    pub index: usize,
+    pub field: Field,
+    pub value: String,
+    pub indices: Vec<usize>,
+}
+
+struct Results {
+    generation: u64,
+    query: Query,
+    matches: Arc<Vec<Match>>,
+}
+
+/// Scores a list of functions against queries on its own thread, so typing never waits on a
+/// large list. Only the latest query is scored, older ones are cancelled.
+pub(crate) struct Matcher {
+    lambdas: Arc<Vec<Lambda>>,
+    latest: Arc<AtomicU64>,
+    results: Arc<(Mutex<Results>, Condvar)>,
+    requests: mpsc::Sender<(u64, Query, Option<SortOrder>)>,
+}
+
+// the matching functions, best match first unless sorted otherwise, and the pinned ones before
+// the rest while there is no text, or none if a newer query came in while scoring
+fn score(
+    lambdas: &[Lambda],
+    candidates: Option<Vec<usize>>,
+    query: &Query,
+    sort: Option<SortOrder>,
+    cancelled: impl Fn() -> bool,
+) -> Option<Vec<Match>> {
+    let matcher = SkimMatcherV2::default();
+    let pattern = query.pattern();
+    let candidates = candidates.unwrap_or_else(|| (0..lambdas.len()).collect());
+
+    let mut scored = vec![];
+    for (scored_so_far, index) in candidates.into_iter().enumerate() {
+        if scored_so_far % CANCEL_INTERVAL == 0 && cancelled() {
+            return None;
+        }
+
+        let lambda = &lambdas[index];
+        if !query.matches(lambda) {
+            continue;
+        }
+
+        // without any text every function matches, by its name
+        if query.text.is_empty() {
+            scored.push((
+                0,
+                Match {
+                    index,
+                    field: Field::Name,
+                    value: lambda.name.clone(),
+                    indices: vec![],
+                },
+            ));
+            continue;
+        }
+
+        // the best scoring value of any field in scope, the earlier field on a tie
+        let mut best: Option<(i64, Match)> = None;
+        for field in query.scope.fields() {
+            for value in field.values(lambda) {
+                if let Some((score, indices)) = matcher.fuzzy_indices(&value, &pattern) {
+                    if !matches!(&best, Some((best, _)) if score <= *best) {
+                        best = Some((
+                            score,
+                            Match {
+                                index,
+                                field: *field,
+                                value,
+                                indices,
+                            },
+                        ));
+                    }
+                }
+            }
+        }
+
+        if let Some(best) = best {
+            scored.push(best);
+        }
+    }
+
+    // the sorts are stable, so equal scores keep the order of the list, and equal values the
+    // order of the scores
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+    if let Some(sort) = sort {
+        scored.sort_by(|(_, a), (_, b)| {
+            let ordering = sort.column.compare(&lambdas[a.index], &lambdas[b.index]);
+            if sort.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+
+    if query.text.is_empty() && !query.pinned.is_empty() {
+        scored.sort_by_key(|(_, m)| {
+            query
+                .pinned
+                .iter()
+                .position(|name| *name == lambdas[m.index].name)
+                .unwrap_or(usize::MAX)
+        });
+    }
+
+    Some(scored.into_iter().map(|(_, m)| m).collect())
+}
+
+impl Matcher {
+    pub fn new(lambdas: Arc<Vec<Lambda>>, sort: Option<SortOrder>) -> Self {
+        let query = Query::default();
+        let matches = score(&lambdas, None, &query, sort, || false).unwrap_or_default();
+
+        let latest = Arc::new(AtomicU64::new(0));
+        let results = Arc::new((
+            Mutex::new(Results {
+                generation: 0,
+                query,
+                matches: Arc::new(matches),
+            }),
+            Condvar::new(),
+        ));
+        let (requests, pending) = mpsc::channel::<(u64, Query, Option<SortOrder>)>();
+
+        let (thread_lambdas, thread_latest, thread_results) =
+            (lambdas.clone(), latest.clone(), results.clone());
+
+        // the thread stops once the matcher, and with it the sender, is dropped
+        std::thread::spawn(move || {
+            while let Ok(mut request) = pending.recv() {
+                while let Ok(newer) = pending.try_recv() {
+                    request = newer;
+                }
+                let (generation, query, sort) = request;
+
+                // a query that only adds to the previous one can only match fewer functions,
+                // so only the previous matches need scoring
+                let candidates = {
+                    let (lock, _) = &*thread_results;
+                    let previous = lock.lock().unwrap();
+                    query
+                        .narrows(&previous.query)
+                        .then(|| previous.matches.iter().map(|m| m.index).collect())
+                };
+
+                let matches = score(&thread_lambdas, candidates, &query, sort, || {
+                    thread_latest.load(Ordering::SeqCst) != generation
+                });
+
+                if let Some(matches) = matches {
+                    let (lock, ready) = &*thread_results;
+                    *lock.lock().unwrap() = Results {
+                        generation,
+                        query,
+                        matches: Arc::new(matches),
+                    };
+                    ready.notify_all();
+                }
+            }
+        });
+
+        Self {
+            lambdas,
+            latest,
+            results,
+            requests,
+        }
+    }
+
+    pub fn lambdas(&self) -> &Arc<Vec<Lambda>> {
+        &self.lambdas
+    }
+
+    pub fn lambda(&self, m: &Match) -> &Lambda {
+        &self.lambdas[m.index]
+    }
+
+    /// Score the functions against the query, waiting up to a frame for the results.
+    pub fn search(&self, query: Query, sort: Option<SortOrder>) {
+        let generation = self.latest.fetch_add(1, Ordering::SeqCst) + 1;
+        self.requests.send((generation, query, sort)).unwrap();
+
+        let (lock, ready) = &*self.results;
+        let _ = ready
+            .wait_timeout_while(lock.lock().unwrap(), FRAME_BUDGET, |results| {
+                results.generation < generation
+            })
+            .unwrap();
+    }
+
+    /// The matches of the latest query that has finished scoring.
+    pub fn matches(&self) -> Arc<Vec<Match>> {
+        self.results.0.lock().unwrap().matches.clone()
+    }
+
+    /// Whether the latest query is still being scored.
+    pub fn pending(&self) -> bool {
+        self.results.0.lock().unwrap().generation < self.latest.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::Column, query::SearchScope};
+
+    fn lambda(name: &str, memory: i64, description: &str) -> Lambda {
+        Lambda {
+            name: name.to_string(),
+            memory,
+            description: description.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn lambdas() -> Vec<Lambda> {
+        vec![
+            lambda("orders-place", 512, "takes an order"),
+            lambda("payments-charge", 128, "charges a visa card"),
+            lambda("orders-cancel", 1024, "cancels an order"),
+        ]
+    }
+
+    fn names(lambdas: &[Lambda], matches: Option<Vec<Match>>) -> Vec<&str> {
+        matches
+            .unwrap()
+            .iter()
+            .map(|m| lambdas[m.index].name.as_str())
+            .collect()
+    }
+
+    fn query(text: &str) -> Query {
+        Query::parse(text).unwrap()
+    }
+
+    #[test]
+    fn score_keeps_only_the_matching_functions() {
+        let lambdas = lambdas();
+        let matches = score(&lambdas, None, &query("orders"), None, || false);
+
+        let mut found = names(&lambdas, matches);
+        found.sort();
+        assert_eq!(found, vec!["orders-cancel", "orders-place"]);
+    }
+
+    #[test]
+    fn score_matches_words_separated_by_spaces() {
+        let lambdas = lambdas();
+        let matches = score(&lambdas, None, &query("orders place"), None, || false);
+
+        assert_eq!(names(&lambdas, matches), vec!["orders-place"]);
+    }
+
+    #[test]
+    fn score_only_looks_at_the_candidates() {
+        let lambdas = lambdas();
+        let matches = score(&lambdas, Some(vec![2]), &query("orders"), None, || false);
+
+        assert_eq!(names(&lambdas, matches), vec!["orders-cancel"]);
+    }
+
+    #[test]
+    fn score_reports_the_matching_field() {
+        let lambdas = lambdas();
+        let mut query = query("visa");
+        query.scope = SearchScope::All;
+
+        let matches = score(&lambdas, None, &query, None, || false).unwrap();
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].field, Field::Description);
+        assert_eq!(matches[0].value, "charges a visa card");
+        assert_eq!(matches[0].indices.len(), 4);
+    }
+
+    #[test]
+    fn score_sorts_by_a_column() {
+        let lambdas = lambdas();
+        let ascending = SortOrder {
+            column: Column::Memory,
+            descending: false,
+        };
+        let descending = SortOrder {
+            descending: true,
+            ..ascending
+        };
+
+        assert_eq!(
+            names(
+                &lambdas,
+                score(&lambdas, None, &query(""), Some(ascending), || false)
+            ),
+            vec!["payments-charge", "orders-place", "orders-cancel"]
+        );
+        assert_eq!(
+            names(
+                &lambdas,
+                score(&lambdas, None, &query(""), Some(descending), || false)
+            ),
+            vec!["orders-cancel", "orders-place", "payments-charge"]
+        );
+    }
+
+    #[test]
+    fn score_puts_pinned_functions_first_without_text() {
+        let lambdas = lambdas();
+        let mut pinned = query("");
+        pinned.pinned = vec!["orders-cancel".to_string(), "payments-charge".to_string()];
+
+        assert_eq!(
+            names(&lambdas, score(&lambdas, None, &pinned, None, || false)),
+            vec!["orders-cancel", "payments-charge", "orders-place"]
+        );
+
+        // once there is text the matches are ranked as usual
+        pinned.text = "place".to_string();
+        assert_eq!(
+            names(&lambdas, score(&lambdas, None, &pinned, None, || false)),
+            vec!["orders-place"]
+        );
+    }
+
+    #[test]
+    fn score_stops_when_cancelled() {
+        let lambdas = lambdas();
+
+        assert!(score(&lambdas, None, &query("orders"), None, || true).is_none());
+    }
+}
@@ -0,0 +1,451 @@
+use std::{fs, io::Write, path::Path};
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::core::Lambda;
+
+mod matcher;
+
+pub(crate) use matcher::{Match, Matcher};
+
+/// How many queries are kept in the search history.
+const HISTORY_LENGTH: usize = 50;
+
+const FIELDS: [&str; 7] = [
+    "runtime", "mem", "timeout", "arch", "tag", "layer", "trigger",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+    Equal,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Comparison {
+    fn matches(&self, value: i64, bound: i64) -> bool {
+        match self {
+            Comparison::Equal => value == bound,
+            Comparison::Less => value < bound,
+            Comparison::LessOrEqual => value <= bound,
+            Comparison::Greater => value > bound,
+            Comparison::GreaterOrEqual => value >= bound,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Filter {
+    Runtime(String),
+    Memory(Comparison, i64),
+    Timeout(Comparison, i64),
+    Architecture(String),
+    Tag(String, Option<String>),
+    Layer(String),
+    Trigger(String),
+}
+
+impl Filter {
+    fn matches(&self, lambda: &Lambda) -> bool {
+        match self {
+            Filter::Runtime(runtime) => lambda.runtime.to_lowercase().contains(runtime),
+            Filter::Memory(comparison, bound) => comparison.matches(lambda.memory, *bound),
+            Filter::Timeout(comparison, bound) => comparison.matches(lambda.timeout, *bound),
+            Filter::Architecture(architecture) => {
+                lambda.architecture.eq_ignore_ascii_case(architecture)
+            }
+            Filter::Tag(key, value) => match (lambda.tags.get(key), value) {
+                (Some(tag), Some(value)) => tag == value,
+                (Some(_), None) => true,
+                (None, _) => false,
+            },
+            Filter::Layer(layer) => lambda.layers.iter().any(|arn| {
+                crate::report::layer_name(arn)
+                    .to_lowercase()
+                    .contains(layer)
+            }),
+            Filter::Trigger(trigger) => lambda.triggers.iter().any(|t| t.contains(trigger)),
+        }
+    }
+}
+
+/// An attribute of a function the search text can match.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Field {
+    Name,
+    Arn,
+    Description,
+    Tag,
+    Environment,
+    Trigger,
+}
+
+impl Field {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Field::Name => "name",
+            Field::Arn => "arn",
+            Field::Description => "description",
+            Field::Tag => "tag",
+            Field::Environment => "env",
+            Field::Trigger => "trigger",
+        }
+    }
+
+    pub fn values(&self, lambda: &Lambda) -> Vec<String> {
+        match self {
+            Field::Name => vec![lambda.name.clone()],
+            Field::Arn => vec![lambda.arn.clone()],
+            Field::Description => vec![lambda.description.clone()],
+            Field::Tag => lambda
+                .tags
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect(),
+            Field::Environment => lambda.environment_keys.clone(),
+            Field::Trigger => lambda.trigger_names.clone(),
+        }
+    }
+}
+
+/// Which attributes of a function the search text is matched against.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) enum SearchScope {
+    #[default]
+    Name,
+    All,
+    Arn,
+    Description,
+    Tags,
+    Environment,
+    Triggers,
+}
+
+impl SearchScope {
+    pub fn next(&self) -> Self {
+        match self {
+            SearchScope::Name => SearchScope::All,
+            SearchScope::All => SearchScope::Arn,
+            SearchScope::Arn => SearchScope::Description,
+            SearchScope::Description => SearchScope::Tags,
+            SearchScope::Tags => SearchScope::Environment,
+            SearchScope::Environment => SearchScope::Triggers,
+            SearchScope::Triggers => SearchScope::Name,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SearchScope::Name => "names",
+            SearchScope::All => "all fields",
+            SearchScope::Arn => "arns",
+            SearchScope::Description => "descriptions",
+            SearchScope::Tags => "tags",
+            SearchScope::Environment => "environment keys",
+            SearchScope::Triggers => "triggers",
+        }
+    }
+
+    /// The fields searched, the name first so it wins a tie.
+    pub fn fields(&self) -> &'static [Field] {
+        match self {
+            SearchScope::Name => &[Field::Name],
+            SearchScope::All => &[
+                Field::Name,
+                Field::Arn,
+                Field::Description,
+                Field::Tag,
+                Field::Environment,
+                Field::Trigger,
+            ],
+            SearchScope::Arn => &[Field::Arn],
+            SearchScope::Description => &[Field::Description],
+            SearchScope::Tags => &[Field::Tag],
+            SearchScope::Environment => &[Field::Environment],
+            SearchScope::Triggers => &[Field::Trigger],
+        }
+    }
+}
+
+/// A search query, the fuzzy matched text and the field filters written as `field:value`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Query {
+    pub text: String,
+    filters: Vec<Filter>,
+    // the fields below are set by the search page rather than written in the query
+    pub scope: SearchScope,
+    /// functions shown first, in order, while there is no text to match
+    pub pinned: Vec<String>,
+    /// when set, only these functions match
+    pub only: Option<Vec<String>>,
+}
+
+fn parse_comparison(field: &str, value: &str) -> Result<(Comparison, i64)> {
+    let (comparison, number) = [
+        (">=", Comparison::GreaterOrEqual),
+        ("<=", Comparison::LessOrEqual),
+        (">", Comparison::Greater),
+        ("<", Comparison::Less),
+        ("=", Comparison::Equal),
+    ]
+    .iter()
+    .find_map(|(operator, comparison)| {
+        value
+            .strip_prefix(operator)
+            .map(|number| (*comparison, number))
+    })
+    .unwrap_or((Comparison::Equal, value));
+
+    let number = number
+        .parse()
+        .map_err(|_| anyhow!("{}: expected a number, got \"{}\"", field, number))?;
+
+    Ok((comparison, number))
+}
+
+fn parse_filter(field: &str, value: &str) -> Result<Filter> {
+    if value.is_empty() {
+        bail!("{}: missing a value", field);
+    }
+
+    let value_lowercase = value.to_lowercase();
+
+    Ok(match field {
+        "runtime" => Filter::Runtime(value_lowercase),
+        "mem" => {
+            let (comparison, bound) = parse_comparison(field, value)?;
+            Filter::Memory(comparison, bound)
+        }
+        "timeout" => {
+            let (comparison, bound) = parse_comparison(field, value)?;
+            Filter::Timeout(comparison, bound)
+        }
+        "arch" => Filter::Architecture(value_lowercase),
+        "tag" => match value.split_once('=') {
+            Some(("", _)) => bail!("tag: missing a key before ="),
+            Some((key, value)) => Filter::Tag(key.to_string(), Some(value.to_string())),
+            None => Filter::Tag(value.to_string(), None),
+        },
+        "layer" => Filter::Layer(value_lowercase),
+        "trigger" => Filter::Trigger(value_lowercase),
+        _ => unreachable!("only the fields in FIELDS are parsed as filters"),
+    })
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut text = String::new();
+        let mut filters = vec![];
+
+        // anything not starting with a known field is text, so arns can be searched for
+        for token in input.split_whitespace() {
+            match token.split_once(':') {
+                Some((field, value)) if FIELDS.contains(&field) => {
+                    filters.push(parse_filter(field, value)?)
+                }
+                _ => {
+                    if !text.is_empty() {
+                        text.push(' ');
+                    }
+                    text.push_str(token);
+                }
+            }
+        }
+
+        Ok(Self {
+            text,
+            filters,
+            ..Self::default()
+        })
+    }
+
+    /// The text the functions are fuzzy matched against, the words run together as spaces
+    /// would otherwise have to match too, e.g. "orders place" finds `orders-place`.
+    pub fn pattern(&self) -> String {
+        self.text.split_whitespace().collect()
+    }
+
+    pub fn matches(&self, lambda: &Lambda) -> bool {
+        !matches!(&self.only, Some(names) if !names.contains(&lambda.name))
+            && self.filters.iter().all(|filter| filter.matches(lambda))
+    }
+
+    /// Whether everything matching this query also matches the previous one.
+    pub fn narrows(&self, previous: &Query) -> bool {
+        self.filters == previous.filters
+            && self.scope == previous.scope
+            && self.only == previous.only
+            && self.text.starts_with(&previous.text)
+    }
+}
+
+fn history_path() -> String {
+    format!(
+        "{}/.config/shepherd/history.json",
+        dirs::home_dir().unwrap().to_str().unwrap()
+    )
+}
+
+/// The previous queries, most recent first.
+pub(crate) fn load_history() -> Vec<String> {
+    fs::read_to_string(history_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Put a query at the front of the history, dropping any earlier use of it.
+pub(crate) fn save_history(history: &mut Vec<String>, query: &str) {
+    let query = query.trim();
+    if query.is_empty() {
+        return;
+    }
+
+    history.retain(|q| q != query);
+    history.insert(0, query.to_string());
+    history.truncate(HISTORY_LENGTH);
+
+    let path = history_path();
+    if let Some(parent) = Path::new(&path).parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+
+    // the history is a convenience, failing to write it should not interrupt the search
+    if let Ok(mut file) = fs::File::create(&path) {
+        let _ = file.write_all(serde_json::to_string(history).unwrap().as_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lambda(name: &str, runtime: &str, memory: i64) -> Lambda {
+        Lambda {
+            name: name.to_string(),
+            runtime: runtime.to_string(),
+            memory,
+            timeout: 3,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parse_separates_filters_from_text() {
+        let query = Query::parse("orders runtime:Python mem:>=512 place").unwrap();
+
+        assert_eq!(query.text, "orders place");
+        assert_eq!(
+            query.filters,
+            vec![
+                Filter::Runtime("python".to_string()),
+                Filter::Memory(Comparison::GreaterOrEqual, 512),
+            ]
+        );
+    }
+
+    #[test]
+    fn pattern_runs_the_words_together() {
+        let query = Query::parse("orders  place runtime:python").unwrap();
+
+        assert_eq!(query.text, "orders place");
+        assert_eq!(query.pattern(), "ordersplace");
+    }
+
+    #[test]
+    fn parse_keeps_unknown_fields_as_text() {
+        let query = Query::parse("arn:aws:lambda:eu-west-1:123456789012:function:orders").unwrap();
+
+        assert_eq!(
+            query.text,
+            "arn:aws:lambda:eu-west-1:123456789012:function:orders"
+        );
+        assert!(query.filters.is_empty());
+    }
+
+    #[test]
+    fn parse_reads_comparisons_and_tags() {
+        let query = Query::parse("timeout:<30 mem:1024 tag:team=payments tag:owner").unwrap();
+
+        assert_eq!(
+            query.filters,
+            vec![
+                Filter::Timeout(Comparison::Less, 30),
+                Filter::Memory(Comparison::Equal, 1024),
+                Filter::Tag("team".to_string(), Some("payments".to_string())),
+                Filter::Tag("owner".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_rejects_bad_filters() {
+        assert_eq!(
+            Query::parse("mem:>abc").unwrap_err().to_string(),
+            "mem: expected a number, got \"abc\""
+        );
+        assert_eq!(
+            Query::parse("runtime:").unwrap_err().to_string(),
+            "runtime: missing a value"
+        );
+        assert_eq!(
+            Query::parse("tag:=payments").unwrap_err().to_string(),
+            "tag: missing a key before ="
+        );
+    }
+
+    #[test]
+    fn matches_applies_every_filter() {
+        let query = Query::parse("runtime:python mem:>=512").unwrap();
+
+        assert!(query.matches(&lambda("orders", "python3.12", 1024)));
+        assert!(!query.matches(&lambda("orders", "python3.12", 256)));
+        assert!(!query.matches(&lambda("orders", "nodejs20.x", 1024)));
+    }
+
+    #[test]
+    fn matches_only_the_listed_functions() {
+        let query = Query {
+            only: Some(vec!["orders".to_string()]),
+            ..Query::default()
+        };
+
+        assert!(query.matches(&lambda("orders", "python3.12", 128)));
+        assert!(!query.matches(&lambda("payments", "python3.12", 128)));
+    }
+
+    #[test]
+    fn narrows_when_text_is_appended() {
+        let previous = Query::parse("ord runtime:python").unwrap();
+
+        assert!(Query::parse("orders runtime:python")
+            .unwrap()
+            .narrows(&previous));
+        assert!(!Query::parse("or runtime:python")
+            .unwrap()
+            .narrows(&previous));
+        assert!(!Query::parse("payments runtime:python")
+            .unwrap()
+            .narrows(&previous));
+    }
+
+    #[test]
+    fn narrows_only_with_the_same_filters_and_scope() {
+        let previous = Query::parse("ord runtime:python").unwrap();
+
+        assert!(!Query::parse("orders runtime:node")
+            .unwrap()
+            .narrows(&previous));
+        assert!(!Query::parse("orders").unwrap().narrows(&previous));
+
+        let mut scoped = Query::parse("orders runtime:python").unwrap();
+        scoped.scope = SearchScope::All;
+        assert!(!scoped.narrows(&previous));
+
+        let mut only = Query::parse("orders runtime:python").unwrap();
+        only.only = Some(vec![]);
+        assert!(!only.narrows(&previous));
+    }
+}
@@ -0,0 +1,425 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::core::{DriftGroup, DriftSetting, FunctionSnapshot, Lambda};
+
+enum Segment {
+    Literal(String),
+    Placeholder(String),
+}
+
+fn parse_pattern(pattern: &str) -> Vec<Segment> {
+    let mut segments = vec![];
+    let mut rest = pattern;
+
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        if start > 0 {
+            segments.push(Segment::Literal(rest[..start].to_string()));
+        }
+        segments.push(Segment::Placeholder(rest[start + 1..end].to_string()));
+        rest = &rest[end + 1..];
+    }
+
+    if !rest.is_empty() {
+        segments.push(Segment::Literal(rest.to_string()));
+    }
+
+    segments
+}
+
+// the value of every placeholder, trying the shortest value of each placeholder first and
+// backtracking when the rest of the name doesn't match, a stage has to be one of the stages
+fn placeholder_values<'a>(
+    segments: &[Segment],
+    rest: &'a str,
+    stages: &[String],
+) -> Option<Vec<&'a str>> {
+    let (segment, remaining) = match segments.split_first() {
+        Some(split) => split,
+        None => return rest.is_empty().then(Vec::new),
+    };
+
+    match segment {
+        Segment::Literal(literal) => {
+            placeholder_values(remaining, rest.strip_prefix(literal.as_str())?, stages)
+        }
+        Segment::Placeholder(placeholder) => (1..=rest.len())
+            .filter(|end| rest.is_char_boundary(*end))
+            .filter(|end| {
+                placeholder != "stage"
+                    || stages.is_empty()
+                    || stages.iter().any(|stage| stage == &rest[..*end])
+            })
+            .find_map(|end| {
+                let mut values = placeholder_values(remaining, &rest[end..], stages)?;
+                values.insert(0, &rest[..end]);
+                Some(values)
+            }),
+    }
+}
+
+/// Split a function name into its logical name and stage using a pattern such as
+/// `{service}-{stage}-{name}`, the logical name keeps a `{stage}` in place of the stage.
+/// The stage has to be one of `stages` unless there are none, in which case it can be anything.
+pub(crate) fn stage_from_name(
+    name: &str,
+    pattern: &str,
+    stages: &[String],
+) -> Option<(String, String)> {
+    let segments = parse_pattern(pattern);
+    let mut values = placeholder_values(&segments, name, stages)?.into_iter();
+    let mut logical = String::new();
+    let mut stage = None;
+
+    for segment in &segments {
+        match segment {
+            Segment::Literal(literal) => logical.push_str(literal),
+            Segment::Placeholder(placeholder) => {
+                let value = values.next()?;
+                if placeholder == "stage" {
+                    stage = Some(value.to_string());
+                    logical.push_str("{stage}");
+                } else {
+                    logical.push_str(value);
+                }
+            }
+        }
+    }
+
+    stage.map(|stage| (logical, stage))
+}
+
+// where the stage appears as a whole segment of a name, between separators like `-` or `_`,
+// so that `prod` is found in `svc-prod` but not in `product-svc`
+fn stage_segments<'a>(value: &'a str, stage: &'a str) -> impl Iterator<Item = usize> + 'a {
+    let separator = |c: Option<char>| !matches!(c, Some(c) if c.is_alphanumeric());
+    value
+        .match_indices(stage)
+        .map(|(start, _)| start)
+        .filter(move |start| {
+            !stage.is_empty()
+                && separator(value[..*start].chars().next_back())
+                && separator(value[start + stage.len()..].chars().next())
+        })
+}
+
+/// Group functions by logical name, keeping only those deployed to more than one stage.
+pub(crate) fn group_by_stage(
+    functions: Vec<(Lambda, Option<String>)>,
+    pattern: &str,
+    stages: &[String],
+) -> Vec<(String, Vec<(String, Lambda)>)> {
+    let mut groups: BTreeMap<String, Vec<(String, Lambda)>> = BTreeMap::new();
+
+    for (lambda, stage_tag) in functions {
+        let grouping = match stage_tag {
+            // the stage comes from a tag, so the logical name is the name without the stage
+            Some(stage) => Some((without_stage(&lambda.name, &stage), stage)),
+            None => stage_from_name(&lambda.name, pattern, stages),
+        };
+
+        if let Some((logical, stage)) = grouping {
+            groups.entry(logical).or_default().push((stage, lambda));
+        }
+    }
+
+    groups
+        .into_iter()
+        .filter(|(_, stages)| stages.len() > 1)
+        .map(|(name, mut stages)| {
+            stages.sort_by(|a, b| a.0.cmp(&b.0));
+            (name, stages)
+        })
+        .collect()
+}
+
+// layers are compared by name and version, the account and region are noise
+pub(crate) fn layer_name(arn: &str) -> String {
+    let parts = arn.split(':').collect::<Vec<_>>();
+    match parts.len() {
+        n if n >= 2 => format!("{}:{}", parts[n - 2], parts[n - 1]),
+        _ => arn.to_string(),
+    }
+}
+
+// resources named after the stage are expected to differ between stages, so compare them
+// with the stage taken out
+fn without_stage(value: &str, stage: &str) -> String {
+    let mut result = String::new();
+    let mut end = 0;
+    for start in stage_segments(value, stage) {
+        result.push_str(&value[end..start]);
+        result.push_str("{stage}");
+        end = start + stage.len();
+    }
+    result.push_str(&value[end..]);
+    result
+}
+
+fn joined(values: BTreeSet<String>) -> String {
+    values.into_iter().collect::<Vec<_>>().join(", ")
+}
+
+/// Reads a setting from a function deployed to the given stage.
+type SettingValue = fn(&FunctionSnapshot, &str) -> String;
+
+pub(crate) fn drift_group(
+    name: String,
+    stages: Vec<(String, FunctionSnapshot)>,
+    errors: Vec<String>,
+) -> DriftGroup {
+    let settings: [(&str, SettingValue); 5] = [
+        ("Runtime", |s, _| s.config.runtime.clone()),
+        ("Memory", |s, _| s.config.memory.to_string()),
+        ("Layers", |s, stage| {
+            joined(
+                s.config
+                    .layers
+                    .iter()
+                    .map(|arn| without_stage(&layer_name(arn), stage))
+                    .collect(),
+            )
+        }),
+        ("Environment keys", |s, _| {
+            joined(
+                s.config
+                    .environment
+                    .iter()
+                    .map(|(k, _)| k.clone())
+                    .collect(),
+            )
+        }),
+        ("Triggers", |s, stage| {
+            joined(
+                s.event_source_mappings
+                    .iter()
+                    .map(|m| without_stage(&format!("{} {}", m.type_name(), m.name()), stage))
+                    .collect(),
+            )
+        }),
+    ];
+
+    DriftGroup {
+        name,
+        stages: stages.iter().map(|(stage, _)| stage.clone()).collect(),
+        functions: stages
+            .iter()
+            .map(|(_, snapshot)| snapshot.function.name())
+            .collect(),
+        settings: settings
+            .iter()
+            .map(|(setting, value)| DriftSetting {
+                name: setting.to_string(),
+                values: stages
+                    .iter()
+                    .map(|(stage, snapshot)| value(snapshot, stage))
+                    .collect(),
+            })
+            .collect(),
+        errors,
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('|', "\\|")
+}
+
+pub(crate) fn markdown(groups: &[DriftGroup], grouped_by: &str) -> String {
+    let drifting = groups.iter().filter(|g| g.drifts()).count();
+
+    let mut lines = vec![
+        "# Environment drift report".to_string(),
+        "".to_string(),
+        format!(
+            "Generated {} from {} functions grouped by {}, {} of {} drift between stages.",
+            chrono::Local::now().format("%Y-%m-%d %H:%M"),
+            groups.iter().map(|g| g.functions.len()).sum::<usize>(),
+            grouped_by,
+            drifting,
+            groups.len()
+        ),
+    ];
+
+    for group in groups.iter().filter(|g| g.needs_attention()) {
+        lines.push("".to_string());
+        lines.push(format!("## {}", group.name));
+        lines.push("".to_string());
+        if group.drifts() {
+            lines.push(format!("| Setting | {} |", group.stages.join(" | ")));
+            lines.push(format!("|---|{}", "---|".repeat(group.stages.len())));
+
+            for setting in group.settings.iter().filter(|s| s.differs()) {
+                lines.push(format!(
+                    "| {} | {} |",
+                    setting.name,
+                    setting
+                        .values
+                        .iter()
+                        .map(|v| escape(v))
+                        .collect::<Vec<_>>()
+                        .join(" | ")
+                ));
+            }
+        }
+
+        if !group.errors.is_empty() {
+            if group.drifts() {
+                lines.push("".to_string());
+            }
+            lines.push("Could not be compared:".to_string());
+            lines.push("".to_string());
+            lines.extend(group.errors.iter().map(|e| format!("- {}", e)));
+        }
+    }
+
+    lines.push("".to_string());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stages() -> Vec<String> {
+        crate::config::DriftConfig::default().stages
+    }
+
+    fn lambda(name: &str) -> Lambda {
+        Lambda {
+            name: name.to_string(),
+            arn: format!("arn:aws:lambda:eu-west-1:123456789012:function:{}", name),
+            runtime: "nodejs20.x".to_string(),
+            memory: 128,
+            timeout: 3,
+            ..Default::default()
+        }
+    }
+
+    fn grouped(groups: Vec<(String, Vec<(String, Lambda)>)>) -> Vec<(String, Vec<String>)> {
+        groups
+            .into_iter()
+            .map(|(name, stages)| (name, stages.into_iter().map(|(stage, _)| stage).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn stage_from_name_finds_a_known_stage_after_dashes() {
+        assert_eq!(
+            stage_from_name(
+                "payments-api-prod-charge",
+                "{service}-{stage}-{name}",
+                &stages()
+            ),
+            Some((
+                "payments-api-{stage}-charge".to_string(),
+                "prod".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn stage_from_name_keeps_dashes_in_the_name() {
+        assert_eq!(
+            stage_from_name(
+                "orders-dev-place-order",
+                "{service}-{stage}-{name}",
+                &stages()
+            ),
+            Some(("orders-{stage}-place-order".to_string(), "dev".to_string()))
+        );
+    }
+
+    #[test]
+    fn stage_from_name_needs_a_known_stage() {
+        assert_eq!(
+            stage_from_name("payments-api-charge", "{service}-{stage}-{name}", &stages()),
+            None
+        );
+    }
+
+    #[test]
+    fn stage_from_name_takes_any_stage_when_none_are_known() {
+        assert_eq!(
+            stage_from_name("payments-blue-charge", "{service}-{stage}-{name}", &[]),
+            Some(("payments-{stage}-charge".to_string(), "blue".to_string()))
+        );
+    }
+
+    #[test]
+    fn stage_from_name_matches_literals_around_the_placeholders() {
+        assert_eq!(
+            stage_from_name("app_prod_worker", "app_{stage}_{name}", &stages()),
+            Some(("app_{stage}_worker".to_string(), "prod".to_string()))
+        );
+        assert_eq!(
+            stage_from_name("web_prod_worker", "app_{stage}_{name}", &stages()),
+            None
+        );
+    }
+
+    #[test]
+    fn stage_from_name_needs_a_stage_placeholder() {
+        assert_eq!(
+            stage_from_name("payments-charge", "{service}-{name}", &stages()),
+            None
+        );
+    }
+
+    #[test]
+    fn without_stage_replaces_whole_segments_only() {
+        assert_eq!(
+            without_stage("product-svc-prod", "prod"),
+            "product-svc-{stage}"
+        );
+        assert_eq!(
+            without_stage("prod_queue_prod", "prod"),
+            "{stage}_queue_{stage}"
+        );
+        assert_eq!(without_stage("production", "prod"), "production");
+    }
+
+    #[test]
+    fn group_by_stage_keeps_functions_deployed_to_more_than_one_stage() {
+        let functions = vec![
+            (lambda("payments-api-prod-charge"), None),
+            (lambda("payments-api-dev-charge"), None),
+            (lambda("payments-api-prod-refund"), None),
+            (lambda("unrelated"), None),
+        ];
+
+        assert_eq!(
+            grouped(group_by_stage(
+                functions,
+                "{service}-{stage}-{name}",
+                &stages()
+            )),
+            vec![(
+                "payments-api-{stage}-charge".to_string(),
+                vec!["dev".to_string(), "prod".to_string()]
+            )]
+        );
+    }
+
+    #[test]
+    fn group_by_stage_takes_the_stage_tag_out_of_the_name() {
+        let functions = vec![
+            (lambda("product-svc-prod"), Some("prod".to_string())),
+            (lambda("product-svc-test"), Some("test".to_string())),
+        ];
+
+        assert_eq!(
+            grouped(group_by_stage(
+                functions,
+                "{service}-{stage}-{name}",
+                &stages()
+            )),
+            vec![(
+                "product-svc-{stage}".to_string(),
+                vec!["prod".to_string(), "test".to_string()]
+            )]
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use std::{collections::BTreeMap, fs, io::Write, path::Path};
+
+use anyhow::Result;
+
+use crate::core::Lambda;
+
+/// The version each alias pointed at before traffic started shifting away from it, so it can be
+/// rolled back to. Kept here rather than on the alias, which a stack may own.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct PreviousVersions(BTreeMap<String, String>);
+
+// the alias arn, so the same names in another account or region don't collide
+fn key(lambda: &Lambda, alias: &str) -> String {
+    format!("{}:{}", lambda.arn, alias)
+}
+
+fn previous_versions_path() -> String {
+    format!(
+        "{}/.config/shepherd/previous-versions.json",
+        dirs::home_dir().unwrap().to_str().unwrap()
+    )
+}
+
+impl PreviousVersions {
+    pub fn load() -> Self {
+        fs::read_to_string(previous_versions_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = previous_versions_path();
+
+        if let Some(parent) = Path::new(&path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = fs::File::create(&path)?;
+        file.write_all(serde_json::to_string_pretty(self)?.as_bytes())?;
+
+        Ok(())
+    }
+
+    pub fn get(&self, lambda: &Lambda, alias: &str) -> Option<&str> {
+        self.0
+            .get(&key(lambda, alias))
+            .map(|version| version.as_str())
+    }
+
+    /// Record the version the alias is rolled back to, or forget it once there is none.
+    pub fn set(&mut self, lambda: &Lambda, alias: &str, version: Option<&str>) {
+        match version {
+            Some(version) => {
+                self.0.insert(key(lambda, alias), version.to_string());
+            }
+            None => {
+                self.0.remove(&key(lambda, alias));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lambda(arn: &str) -> Lambda {
+        Lambda {
+            name: "orders".to_string(),
+            arn: arn.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn set_records_and_forgets_a_version() {
+        let orders = lambda("arn:aws:lambda:eu-west-1:123456789012:function:orders");
+        let mut previous_versions = PreviousVersions::default();
+
+        previous_versions.set(&orders, "live", Some("3"));
+        assert_eq!(previous_versions.get(&orders, "live"), Some("3"));
+        assert_eq!(previous_versions.get(&orders, "staging"), None);
+
+        previous_versions.set(&orders, "live", None);
+        assert_eq!(previous_versions.get(&orders, "live"), None);
+    }
+
+    #[test]
+    fn get_tells_accounts_apart() {
+        let mut previous_versions = PreviousVersions::default();
+        previous_versions.set(
+            &lambda("arn:aws:lambda:eu-west-1:123456789012:function:orders"),
+            "live",
+            Some("3"),
+        );
+
+        assert_eq!(
+            previous_versions.get(
+                &lambda("arn:aws:lambda:eu-west-1:210987654321:function:orders"),
+                "live"
+            ),
+            None
+        );
+    }
+}
@@ -1,43 +1,519 @@
+use std::{collections::HashMap, sync::Arc, time::Instant};
+
 use anyhow::Result;
 
 use crate::{
     aws::AWS,
-    core::{Action, DashboardState, ErrorState, SearchState, SearchingState, State},
+    config::Config,
+    core::{
+        Action, Alias, AsyncConfig, CompareState, Concurrency, ConfigDiffState, ConfigState,
+        DashboardState, DeadLetterQueue, DeadLetterState, DriftGroup, DriftState, ErrorState,
+        FleetState, FunctionConfiguration, Lambda, Preview, QualifiedLambda, RecentActivity,
+        SearchState, SearchingState, State, Version, VersionState,
+    },
+    favourites::{self, Favourites},
+    report,
+    rollback::PreviousVersions,
 };
+use aws_sdk_lambda::types::LastUpdateStatus;
+use futures::StreamExt;
+
+/// How often, and how many times, to check whether a configuration update has finished.
+const UPDATE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+const UPDATE_POLL_ATTEMPTS: u32 = 60;
+
+/// How long the recent activity shown in the search results is reused before being fetched again.
+const ACTIVITY_TTL: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// How many functions are fetched at once when building a report across the account.
+const REPORT_CONCURRENCY: usize = 8;
 
 pub struct StateManager {
     aws: AWS,
+    config: Config,
     action_rx: tokio::sync::mpsc::UnboundedReceiver<Action>,
     state_tx: tokio::sync::mpsc::UnboundedSender<State>,
+    /// recent activity of the functions shown in the search results, by name
+    activity: HashMap<String, (Instant, RecentActivity)>,
+    /// the functions last shown on the search page
+    lambdas: Arc<Vec<Lambda>>,
+    /// the preview shown next to the search results, and the one being fetched, which is
+    /// aborted when another is asked for or the search page is left
+    preview: Option<Preview>,
+    preview_task: Option<tokio::task::JoinHandle<Preview>>,
+    /// the favourites of the account last searched or viewed
+    favourites: Favourites,
+    /// the versions aliases are rolled back to
+    previous_versions: PreviousVersions,
+    /// the progress of configuration updates running on their own task, by function name,
+    /// shown while the configuration page of that function is still open
+    update_tx: tokio::sync::mpsc::UnboundedSender<(String, State)>,
+    update_rx: tokio::sync::mpsc::UnboundedReceiver<(String, State)>,
+    configuration_open: Option<String>,
+}
+
+/// What the state manager is woken by.
+enum Wakeup {
+    Action(Box<Action>),
+    Update(Box<(String, State)>),
+    Preview(Box<std::result::Result<Preview, tokio::task::JoinError>>),
+}
+
+fn config_state(
+    lambda: &Lambda,
+    config: &FunctionConfiguration,
+    updating: bool,
+    status: Option<String>,
+) -> State {
+    State::Config(ConfigState {
+        lambda: lambda.clone(),
+        config: config.clone(),
+        updating,
+        status,
+    })
+}
+
+// apply the configuration and wait for the function to finish updating, reporting progress
+// on the way
+async fn poll_update(
+    aws: &AWS,
+    update_tx: &tokio::sync::mpsc::UnboundedSender<(String, State)>,
+    lambda: &Lambda,
+    config: &FunctionConfiguration,
+) -> Result<String> {
+    aws.update_configuration(lambda, config).await?;
+
+    for attempt in 1..=UPDATE_POLL_ATTEMPTS {
+        let (status, reason) = aws.last_update_status(lambda).await?;
+        match status {
+            LastUpdateStatus::Successful => {
+                return Ok("configuration updated".to_string());
+            }
+            LastUpdateStatus::Failed => {
+                return Ok(format!(
+                    "configuration update failed: {}",
+                    reason.unwrap_or_default()
+                ));
+            }
+            _ => {
+                let _ = update_tx.send((
+                    lambda.name.clone(),
+                    config_state(
+                        lambda,
+                        config,
+                        true,
+                        Some(format!(
+                            "updating configuration{}",
+                            ".".repeat(attempt as usize % 4)
+                        )),
+                    ),
+                ));
+                tokio::time::sleep(UPDATE_POLL_INTERVAL).await;
+            }
+        }
+    }
+
+    Ok("configuration update is still in progress".to_string())
+}
+
+// update the configuration, then show what the function ended up with, which is the old
+// configuration if the update failed
+async fn update_configuration(
+    aws: AWS,
+    update_tx: tokio::sync::mpsc::UnboundedSender<(String, State)>,
+    lambda: Lambda,
+    config: FunctionConfiguration,
+) {
+    let status = match poll_update(&aws, &update_tx, &lambda, &config).await {
+        Ok(status) => status,
+        Err(e) => format!("failed to update configuration: {}", e),
+    };
+
+    let name = lambda.name.clone();
+    let state = match aws.configuration(&lambda).await {
+        Ok(config) => {
+            // the cached function list holds the old memory and timeout
+            aws.clear_cache().await;
+            let lambda = Lambda {
+                memory: config.memory,
+                timeout: config.timeout,
+                ..lambda
+            };
+            config_state(&lambda, &config, false, Some(status))
+        }
+        Err(e) => State::Error(ErrorState {
+            error_message: e.to_string(),
+        }),
+    };
+    let _ = update_tx.send((name, state));
 }
 
 impl StateManager {
     pub fn new(
         aws: AWS,
+        config: Config,
         state_tx: tokio::sync::mpsc::UnboundedSender<State>,
         action_rx: tokio::sync::mpsc::UnboundedReceiver<Action>,
     ) -> Self {
+        let (update_tx, update_rx) = tokio::sync::mpsc::unbounded_channel();
         Self {
             aws,
+            config,
             action_rx,
             state_tx,
+            activity: HashMap::new(),
+            lambdas: Arc::new(vec![]),
+            preview: None,
+            preview_task: None,
+            favourites: Favourites::default(),
+            previous_versions: PreviousVersions::load(),
+            update_tx,
+            update_rx,
+            configuration_open: None,
+        }
+    }
+
+    fn search_state(&self) -> SearchState {
+        SearchState {
+            lambdas: self.lambdas.clone(),
+            config: self.config.search.clone(),
+            activity: self
+                .activity
+                .iter()
+                .map(|(name, (_, activity))| (name.clone(), activity.clone()))
+                .collect(),
+            preview: self.preview.clone(),
+            favourites: self.favourites.clone(),
+        }
+    }
+
+    // the favourites of the account of the function, loaded when the account changes
+    fn favourites_of(&mut self, arn: &str) -> &mut Favourites {
+        if let Some(account) = favourites::account(arn) {
+            if account != self.favourites.account {
+                self.favourites = Favourites::load(account);
+            }
+        }
+        &mut self.favourites
+    }
+
+    async fn dashboard(&self, lambda: Lambda, status: Option<String>) -> Result<DashboardState> {
+        // only the metrics tab is required, the other tabs are loaded best-effort so that a
+        // missing permission for one of them shows on that tab rather than hiding the dashboard
+        let (required, async_invocation, concurrency, versions) = tokio::join!(
+            async {
+                tokio::try_join!(
+                    self.aws.metrics(&lambda, &self.config.metrics),
+                    self.aws.event_source_mappings(&lambda),
+                )
+            },
+            async {
+                tokio::try_join!(
+                    self.aws.async_config(&lambda),
+                    self.aws.async_metrics(&lambda),
+                )
+            },
+            self.aws.concurrency(&lambda),
+            async { tokio::try_join!(self.aws.versions(&lambda), self.aws.aliases(&lambda)) },
+        );
+
+        let (metrics, event_source_mappings) = required?;
+        let (async_config, async_metrics, async_error) = match async_invocation {
+            Ok((config, metrics)) => (config, metrics, None),
+            Err(e) => (AsyncConfig::default(), vec![], Some(e.to_string())),
+        };
+        let (concurrency, concurrency_error) = match concurrency {
+            Ok(concurrency) => (concurrency, None),
+            Err(e) => (Concurrency::default(), Some(e.to_string())),
+        };
+        let (versions, aliases, versions_error) = match versions {
+            Ok((versions, aliases)) => (versions, aliases, None),
+            Err(e) => (vec![], vec![], Some(e.to_string())),
+        };
+
+        let queues = self.aws.queue_health(&event_source_mappings).await;
+        let starred = self.favourites.is_starred(&lambda.name);
+
+        Ok(DashboardState {
+            lambda,
+            metrics,
+            event_source_mappings,
+            async_config,
+            async_metrics,
+            async_error,
+            queues,
+            concurrency,
+            concurrency_error,
+            versions,
+            aliases,
+            versions_error,
+            baseline: None,
+            baseline_metrics: vec![],
+            status,
+            starred,
+        })
+    }
+
+    async fn dead_letter(
+        &self,
+        lambda: Lambda,
+        queues: Vec<DeadLetterQueue>,
+        selected: usize,
+        status: Option<String>,
+    ) -> Result<DeadLetterState> {
+        let messages = match queues.get(selected) {
+            Some(queue) => self.aws.peek_messages(&queue.arn).await?,
+            None => vec![],
+        };
+
+        Ok(DeadLetterState {
+            lambda,
+            queues,
+            selected,
+            messages,
+            status,
+        })
+    }
+
+    async fn version(
+        &self,
+        lambda: Lambda,
+        alias: Option<Alias>,
+        version: Option<Version>,
+        versions: Vec<Version>,
+        status: Option<String>,
+    ) -> Result<VersionState> {
+        let version_metrics = self
+            .aws
+            .version_metrics(&lambda, alias.as_ref(), version.as_ref())
+            .await?;
+        let alias = alias.map(|alias| {
+            let previous_version = self.previous_versions.get(&lambda, &alias.name);
+            alias.with_previous_version(previous_version)
+        });
+
+        Ok(VersionState {
+            lambda,
+            alias,
+            version,
+            versions,
+            version_metrics,
+            status,
+        })
+    }
+
+    fn send_version(&self, version: Result<VersionState>) {
+        match version {
+            Ok(version) => {
+                self.state_tx.send(State::Version(version)).unwrap();
+            }
+            Err(e) => {
+                self.state_tx
+                    .send(State::Error(ErrorState {
+                        error_message: e.to_string(),
+                    }))
+                    .unwrap();
+            }
+        }
+    }
+
+    fn grouped_by(&self) -> String {
+        match &self.config.drift.stage_tag {
+            Some(tag) => format!("tag {}", tag),
+            None => format!("name pattern {}", self.config.drift.pattern),
+        }
+    }
+
+    async fn drift_report(&self) -> Result<Vec<DriftGroup>> {
+        let lambdas = self.aws.lambda_functions().await?;
+
+        // the stage tag of every function, when grouping by tag rather than name, a function
+        // whose tags can't be read is reported on its own rather than failing the report
+        let (functions, untagged) = match &self.config.drift.stage_tag {
+            Some(tag) => {
+                let tagged: Vec<_> = futures::stream::iter(lambdas)
+                    .map(|lambda| async move {
+                        match self.aws.tags(&lambda).await {
+                            Ok(tags) => Ok((tags.get(tag).cloned(), lambda)),
+                            Err(e) => Err(format!("{}: failed to read tags: {}", lambda.name, e)),
+                        }
+                    })
+                    .buffered(REPORT_CONCURRENCY)
+                    .collect()
+                    .await;
+                let (tagged, untagged): (Vec<_>, Vec<_>) =
+                    tagged.into_iter().partition(|tagged| tagged.is_ok());
+                (
+                    tagged
+                        .into_iter()
+                        .filter_map(|tagged| match tagged {
+                            Ok((Some(stage), lambda)) => Some((lambda, Some(stage))),
+                            _ => None,
+                        })
+                        .collect(),
+                    untagged.into_iter().filter_map(|e| e.err()).collect(),
+                )
+            }
+            None => (
+                lambdas.into_iter().map(|lambda| (lambda, None)).collect(),
+                vec![],
+            ),
+        };
+
+        let groups = report::group_by_stage(
+            functions,
+            &self.config.drift.pattern,
+            &self.config.drift.stages,
+        );
+
+        // a function that can't be read is reported in its group rather than failing the report
+        let mut groups: Vec<_> = futures::stream::iter(groups)
+            .map(|(name, stages)| async move {
+                let snapshots = futures::future::join_all(stages.into_iter().map(
+                    |(stage, lambda)| async move {
+                        let name = lambda.name.clone();
+                        self.aws
+                            .snapshot(&QualifiedLambda {
+                                lambda,
+                                qualifier: None,
+                            })
+                            .await
+                            .map(|snapshot| (stage, snapshot))
+                            .map_err(|e| format!("{}: {}", name, e))
+                    },
+                ))
+                .await;
+                let (snapshots, errors): (Vec<_>, Vec<_>) =
+                    snapshots.into_iter().partition(|snapshot| snapshot.is_ok());
+                report::drift_group(
+                    name,
+                    snapshots.into_iter().filter_map(|s| s.ok()).collect(),
+                    errors.into_iter().filter_map(|e| e.err()).collect(),
+                )
+            })
+            .buffered(REPORT_CONCURRENCY)
+            .collect()
+            .await;
+
+        if !untagged.is_empty() {
+            groups.push(report::drift_group(
+                "Stage unknown".to_string(),
+                vec![],
+                untagged,
+            ));
+        }
+
+        Ok(groups)
+    }
+
+    fn send_dashboard(&self, dashboard: Result<DashboardState>) {
+        match dashboard {
+            Ok(dashboard) => {
+                self.state_tx.send(State::Dashboard(dashboard)).unwrap();
+            }
+            Err(e) => {
+                self.state_tx
+                    .send(State::Error(ErrorState {
+                        error_message: e.to_string(),
+                    }))
+                    .unwrap();
+            }
+        }
+    }
+
+    fn send_dead_letter(&self, dead_letter: Result<DeadLetterState>) {
+        match dead_letter {
+            Ok(dead_letter) => {
+                self.state_tx.send(State::DeadLetter(dead_letter)).unwrap();
+            }
+            Err(e) => {
+                self.state_tx
+                    .send(State::Error(ErrorState {
+                        error_message: e.to_string(),
+                    }))
+                    .unwrap();
+            }
+        }
+    }
+
+    // the next action, showing the preview being fetched and the progress of a configuration
+    // update as they come in
+    async fn next_action(&mut self) -> Action {
+        loop {
+            let preview_task = &mut self.preview_task;
+            let wakeup = tokio::select! {
+                action = self.action_rx.recv() => Wakeup::Action(Box::new(action.unwrap())),
+                Some(state) = self.update_rx.recv() => Wakeup::Update(Box::new(state)),
+                preview = async { preview_task.as_mut().unwrap().await },
+                    if preview_task.is_some() => Wakeup::Preview(Box::new(preview)),
+            };
+
+            match wakeup {
+                Wakeup::Action(action) => return *action,
+                Wakeup::Update(update) => {
+                    let (name, state) = *update;
+                    if self.configuration_open.as_ref() == Some(&name) {
+                        self.state_tx.send(state).unwrap();
+                    }
+                }
+                Wakeup::Preview(preview) => {
+                    self.preview_task = None;
+
+                    // a failed fetch comes back as a preview with its error, so it is shown
+                    if let Ok(preview) = *preview {
+                        self.preview = Some(preview);
+                        self.state_tx
+                            .send(State::Search(self.search_state()))
+                            .unwrap();
+                    }
+                }
+            }
         }
     }
 
     pub async fn run(mut self) {
         loop {
-            let action = self.action_rx.recv().await.unwrap();
+            let action = self.next_action().await;
+
+            // a preview arriving after the search page is left would take the user back to it
+            if !matches!(
+                action,
+                Action::Preview { .. }
+                    | Action::ToggleFavourite { .. }
+                    | Action::FetchRecentActivity { .. }
+                    | Action::SaveSearchConfig { .. }
+            ) {
+                if let Some(task) = self.preview_task.take() {
+                    task.abort();
+                }
+            }
+
+            // an update keeps running once the page is left, but its progress is only shown on
+            // the configuration page of the same function
+            self.configuration_open = match &action {
+                Action::EditConfiguration { lambda }
+                | Action::UpdateConfiguration { lambda, .. } => Some(lambda.name.clone()),
+                _ => None,
+            };
+
             match action {
                 Action::Quit => {
                     self.state_tx.send(State::Quit).unwrap();
                     break;
                 }
                 Action::Search => {
+                    self.activity
+                        .retain(|_, (fetched, _)| fetched.elapsed() < ACTIVITY_TTL);
+
                     let lambdas = self.aws.lambda_functions().await;
                     match lambdas {
                         Ok(lambdas) => {
+                            if let Some(lambda) = lambdas.first() {
+                                self.favourites_of(&lambda.arn);
+                            }
+                            self.lambdas = Arc::new(lambdas);
                             self.state_tx
-                                .send(State::Search(SearchState { lambdas }))
+                                .send(State::Search(self.search_state()))
                                 .unwrap();
                         }
                         Err(e) => {
@@ -50,31 +526,195 @@ impl StateManager {
                     }
                 }
                 Action::PerformSearch { lambda } => {
+                    // the recently viewed are a convenience, failing to save them should not
+                    // keep the dashboard from opening
+                    let favourites = self.favourites_of(&lambda.arn);
+                    favourites.viewed(&lambda.name);
+                    let _ = favourites.save();
+
                     self.state_tx
                         .send(State::Searching(SearchingState {
                             lambda: lambda.clone(),
                         }))
                         .unwrap();
-                    let metrics = self.aws.metrics(&lambda).await;
-                    let event_source_mappings = self.aws.event_source_mappings(&lambda).await;
-                    match (metrics, event_source_mappings) {
-                        (Ok(metrics), Ok(event_source_mappings)) => {
+                    let dashboard = self.dashboard(lambda, None).await;
+                    self.send_dashboard(dashboard);
+                }
+                Action::ShowBaseline { lambda, baseline } => {
+                    let dashboard = match baseline {
+                        Some(baseline) => tokio::try_join!(
+                            self.dashboard(lambda.clone(), None),
+                            self.aws
+                                .baseline_metrics(&lambda, &self.config.metrics, baseline),
+                        )
+                        .map(|(dashboard, baseline_metrics)| DashboardState {
+                            baseline: Some(baseline),
+                            baseline_metrics,
+                            ..dashboard
+                        }),
+                        None => self.dashboard(lambda, None).await,
+                    };
+                    self.send_dashboard(dashboard);
+                }
+                Action::SetReservedConcurrency { lambda, reserved } => {
+                    let status = match self.aws.set_reserved_concurrency(&lambda, reserved).await {
+                        Ok(()) => match reserved {
+                            Some(reserved) => format!("reserved concurrency set to {}", reserved),
+                            None => "reserved concurrency removed".to_string(),
+                        },
+                        Err(e) => format!("failed to set reserved concurrency: {}", e),
+                    };
+                    let dashboard = self.dashboard(lambda, Some(status)).await;
+                    self.send_dashboard(dashboard);
+                }
+                Action::SetProvisionedConcurrency {
+                    lambda,
+                    qualifier,
+                    provisioned,
+                } => {
+                    let status = match self
+                        .aws
+                        .set_provisioned_concurrency(&lambda, &qualifier, provisioned)
+                        .await
+                    {
+                        Ok(()) => format!(
+                            "provisioned concurrency on {} set to {}",
+                            qualifier, provisioned
+                        ),
+                        Err(e) => format!("failed to set provisioned concurrency: {}", e),
+                    };
+                    let dashboard = self.dashboard(lambda, Some(status)).await;
+                    self.send_dashboard(dashboard);
+                }
+                Action::ShowVersion {
+                    lambda,
+                    alias,
+                    version,
+                    versions,
+                } => {
+                    let version = self.version(lambda, alias, version, versions, None).await;
+                    self.send_version(version);
+                }
+                Action::UpdateAlias {
+                    lambda,
+                    alias,
+                    versions,
+                } => {
+                    // a failed update is reported on the page, showing the alias as it was
+                    let (alias, status) = match self.aws.update_alias(&lambda, &alias).await {
+                        Ok(updated) => {
+                            self.previous_versions.set(
+                                &lambda,
+                                &alias.name,
+                                alias.previous_version(),
+                            );
+                            let mut status = format!(
+                                "{} now routes {}",
+                                updated.name,
+                                updated
+                                    .traffic_split()
+                                    .iter()
+                                    .map(|(v, w)| format!("{:.0}% to {}", w * 100.0, v))
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            );
+                            if let Err(e) = self.previous_versions.save() {
+                                status.push_str(&format!(
+                                    ", but failed to save the version to roll back to: {}",
+                                    e
+                                ));
+                            }
+                            (Some(updated), status)
+                        }
+                        Err(e) => {
+                            let current =
+                                self.aws.aliases(&lambda).await.ok().and_then(|aliases| {
+                                    aliases.into_iter().find(|a| a.name == alias.name)
+                                });
+                            (current, format!("failed to update alias: {}", e))
+                        }
+                    };
+                    let version = self
+                        .version(lambda, alias, None, versions, Some(status))
+                        .await;
+                    self.send_version(version);
+                }
+                Action::EditConfiguration { lambda } => {
+                    match self.aws.configuration(&lambda).await {
+                        Ok(config) => self
+                            .state_tx
+                            .send(config_state(&lambda, &config, false, None))
+                            .unwrap(),
+                        Err(e) => {
                             self.state_tx
-                                .send(State::Dashboard(DashboardState {
-                                    lambda,
-                                    metrics,
-                                    event_source_mappings,
+                                .send(State::Error(ErrorState {
+                                    error_message: e.to_string(),
                                 }))
                                 .unwrap();
                         }
-                        (Err(e), _) => {
+                    }
+                }
+                Action::UpdateConfiguration { lambda, config } => {
+                    self.state_tx
+                        .send(config_state(
+                            &lambda,
+                            &config,
+                            true,
+                            Some("updating configuration".to_string()),
+                        ))
+                        .unwrap();
+
+                    // polling can take minutes, the loop keeps handling actions meanwhile
+                    tokio::spawn(update_configuration(
+                        self.aws.clone(),
+                        self.update_tx.clone(),
+                        lambda,
+                        config,
+                    ));
+                }
+                Action::CompareConfiguration {
+                    left,
+                    right,
+                    loaded,
+                } => {
+                    let snapshots = match &loaded {
+                        Some((concurrency, event_source_mappings)) => tokio::try_join!(
+                            self.aws
+                                .snapshot_with(&left, concurrency, event_source_mappings),
+                            self.aws
+                                .snapshot_with(&right, concurrency, event_source_mappings),
+                        ),
+                        None => {
+                            tokio::try_join!(self.aws.snapshot(&left), self.aws.snapshot(&right))
+                        }
+                    };
+                    match snapshots {
+                        Ok((left, right)) => {
+                            self.state_tx
+                                .send(State::ConfigDiff(Box::new(ConfigDiffState { left, right })))
+                                .unwrap();
+                        }
+                        Err(e) => {
                             self.state_tx
                                 .send(State::Error(ErrorState {
                                     error_message: e.to_string(),
                                 }))
                                 .unwrap();
                         }
-                        (_, Err(e)) => {
+                    }
+                }
+                Action::CompareMetrics { left, right } => {
+                    match self.aws.comparison_metrics(&left, &right).await {
+                        Ok(metrics) => {
+                            self.state_tx
+                                .send(State::Compare(CompareState {
+                                    left,
+                                    right,
+                                    metrics,
+                                }))
+                                .unwrap();
+                        }
+                        Err(e) => {
                             self.state_tx
                                 .send(State::Error(ErrorState {
                                     error_message: e.to_string(),
@@ -83,6 +723,160 @@ impl StateManager {
                         }
                     }
                 }
+                // recent activity only decorates the search results, so failures are dropped
+                // and the rows are left without it
+                Action::FetchRecentActivity { lambdas } => {
+                    if let Ok(activity) = self.aws.recent_activity(lambdas).await {
+                        let fetched = Instant::now();
+                        for (lambda, activity) in activity {
+                            self.activity.insert(lambda.name, (fetched, activity));
+                        }
+
+                        self.state_tx
+                            .send(State::Search(self.search_state()))
+                            .unwrap();
+                    }
+                }
+                Action::Preview { lambda } => {
+                    if let Some(task) = self.preview_task.take() {
+                        task.abort();
+                    }
+                    let aws = self.aws.clone();
+                    self.preview_task = Some(tokio::spawn(async move {
+                        match aws.preview(lambda.clone()).await {
+                            Ok(preview) => preview,
+                            Err(e) => Preview {
+                                lambda,
+                                event_source_mappings: vec![],
+                                metrics: vec![],
+                                error: Some(e.to_string()),
+                            },
+                        }
+                    }));
+                }
+                Action::ToggleFavourite { lambda } => {
+                    let favourites = self.favourites_of(&lambda.arn);
+                    favourites.toggle(&lambda.name);
+                    if let Err(e) = favourites.save() {
+                        self.state_tx
+                            .send(State::Error(ErrorState {
+                                error_message: format!("failed to save the favourites: {}", e),
+                            }))
+                            .unwrap();
+                    }
+                }
+                Action::SaveSearchConfig { config } => {
+                    if let Err(e) = Config::save_search(&config) {
+                        self.state_tx
+                            .send(State::Error(ErrorState {
+                                error_message: format!("failed to save the configuration: {}", e),
+                            }))
+                            .unwrap();
+                    }
+                    self.config.search = config;
+                }
+                Action::Fleet { window } => match self.aws.fleet_metrics(window).await {
+                    Ok(functions) => {
+                        self.state_tx
+                            .send(State::Fleet(FleetState { window, functions }))
+                            .unwrap();
+                    }
+                    Err(e) => {
+                        self.state_tx
+                            .send(State::Error(ErrorState {
+                                error_message: e.to_string(),
+                            }))
+                            .unwrap();
+                    }
+                },
+                Action::DriftReport => match self.drift_report().await {
+                    Ok(groups) => {
+                        self.state_tx
+                            .send(State::Drift(DriftState {
+                                groups,
+                                grouped_by: self.grouped_by(),
+                                status: None,
+                            }))
+                            .unwrap();
+                    }
+                    Err(e) => {
+                        self.state_tx
+                            .send(State::Error(ErrorState {
+                                error_message: e.to_string(),
+                            }))
+                            .unwrap();
+                    }
+                },
+                Action::ExportDriftReport { groups, grouped_by } => {
+                    let path = format!(
+                        "drift-report-{}.md",
+                        chrono::Local::now().format("%Y%m%d-%H%M%S")
+                    );
+                    let status = match std::fs::write(&path, report::markdown(&groups, &grouped_by))
+                    {
+                        Ok(()) => format!("report written to {}", path),
+                        Err(e) => format!("failed to write report: {}", e),
+                    };
+                    self.state_tx
+                        .send(State::Drift(DriftState {
+                            groups,
+                            grouped_by,
+                            status: Some(status),
+                        }))
+                        .unwrap();
+                }
+                Action::PeekDeadLetterQueue {
+                    lambda,
+                    queues,
+                    selected,
+                } => {
+                    let dead_letter = self.dead_letter(lambda, queues, selected, None).await;
+                    self.send_dead_letter(dead_letter);
+                }
+                Action::RedriveDeadLetterQueue {
+                    lambda,
+                    queues,
+                    selected,
+                } => {
+                    // failing to redrive is reported on the page rather than as a fatal error
+                    let status = match queues.get(selected) {
+                        Some(DeadLetterQueue {
+                            arn,
+                            source_arn: Some(source_arn),
+                        }) => match self.aws.redrive(arn, source_arn).await {
+                            Ok(task_handle) => format!("started redrive task {}", task_handle),
+                            Err(e) => format!("redrive failed: {}", e),
+                        },
+                        _ => "queue has no source to redrive to".to_string(),
+                    };
+                    let dead_letter = self
+                        .dead_letter(lambda, queues, selected, Some(status))
+                        .await;
+                    self.send_dead_letter(dead_letter);
+                }
+                Action::InvokeWithMessage {
+                    lambda,
+                    queues,
+                    selected,
+                    payload,
+                } => {
+                    let status = match self.aws.invoke(&lambda, &payload).await {
+                        Ok(result) => format!(
+                            "invoked with status {}{}: {}",
+                            result.status_code,
+                            result
+                                .function_error
+                                .map(|e| format!(" ({})", e))
+                                .unwrap_or_default(),
+                            result.payload
+                        ),
+                        Err(e) => format!("invoke failed: {}", e),
+                    };
+                    let dead_letter = self
+                        .dead_letter(lambda, queues, selected, Some(status))
+                        .await;
+                    self.send_dead_letter(dead_letter);
+                }
             }
         }
     }